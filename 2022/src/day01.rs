@@ -1,11 +1,11 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 pub struct AocDay01 {
     calories_by_elves: Vec<i32>,
 }
 
 impl AocDay<i32, i32> for AocDay01 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut vec: Vec<i32> = Vec::new();
 
         let mut calories_sum: Option<i32> = None;
@@ -19,13 +19,7 @@ impl AocDay<i32, i32> for AocDay01 {
                 continue;
             }
 
-            calories_sum = Some(
-                calories_sum.unwrap_or(0)
-                    + input
-                        .trim()
-                        .parse::<i32>()
-                        .expect("Failed to convert to int"),
-            );
+            calories_sum = Some(calories_sum.unwrap_or(0) + input.trim().parse::<i32>()?);
         }
 
         if let Some(v) = calories_sum {
@@ -34,9 +28,9 @@ impl AocDay<i32, i32> for AocDay01 {
 
         vec.sort();
 
-        AocDay01 {
+        Ok(AocDay01 {
             calories_by_elves: vec,
-        }
+        })
     }
     fn part1(&self) -> i32 {
         *self.calories_by_elves.last().unwrap()
@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 pub struct AocDay02 {
     guide: Vec<(Play, Play)>,
@@ -19,12 +19,12 @@ enum Outcome {
 }
 
 impl Play {
-    fn from_str(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self, DayError> {
         match input {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => unreachable!("Unknown play type"),
+            "A" | "X" => Ok(Self::Rock),
+            "B" | "Y" => Ok(Self::Paper),
+            "C" | "Z" => Ok(Self::Scissors),
+            _ => Err(DayError::GenericParseErr("unknown play type")),
         }
     }
 
@@ -88,7 +88,7 @@ impl Outcome {
 }
 
 impl AocDay<i32, i32> for AocDay02 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut guide: Vec<(Play, Play)> = Vec::new();
 
         for line in lines {
@@ -96,17 +96,21 @@ impl AocDay<i32, i32> for AocDay02 {
                 .split_whitespace()
                 .map(Play::from_str)
                 .take(2)
-                .collect();
+                .collect::<Result<_, _>>()?;
 
             let guide_entry: (Play, Play) = (
-                *guide_entry.get(0).expect("line must have first play"),
-                *guide_entry.get(1).expect("line must have second play"),
+                *guide_entry
+                    .first()
+                    .ok_or(DayError::GenericParseErr("line must have first play"))?,
+                *guide_entry
+                    .get(1)
+                    .ok_or(DayError::GenericParseErr("line must have second play"))?,
             );
 
             guide.push(guide_entry);
         }
 
-        AocDay02 { guide }
+        Ok(AocDay02 { guide })
     }
     fn part1(&self) -> i32 {
         self.guide
@@ -134,14 +138,16 @@ mod day02tests {
     const INPUT: &[&str] = &["A Y", "B X", "C Z"];
 
     #[test]
-    fn part1() {
-        let day = AocDay02::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay02::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 15);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay02::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay02::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 12);
+        Ok(())
     }
 }
@@ -1,4 +1,6 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
+
+use std::fmt::Display;
 
 struct Rucksack {
     first_compartment: String,
@@ -7,7 +9,7 @@ struct Rucksack {
 
 impl From<String> for Rucksack {
     fn from(input: String) -> Self {
-        assert!(input.len() % 2 == 0);
+        assert!(input.len().is_multiple_of(2));
 
         let compartments = input.split_at(input.len() / 2);
 
@@ -18,9 +20,9 @@ impl From<String> for Rucksack {
     }
 }
 
-impl ToString for Rucksack {
-    fn to_string(&self) -> String {
-        format!("{}{}", self.first_compartment, self.second_compartment)
+impl Display for Rucksack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.first_compartment, self.second_compartment)
     }
 }
 
@@ -73,14 +75,14 @@ pub struct AocDay03 {
 }
 
 impl AocDay<i32, i32> for AocDay03 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        AocDay03 {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        Ok(AocDay03 {
             rucksacks: lines
                 .map(|x| x.trim().to_string())
                 .filter(|x| !x.is_empty())
                 .map(|x| x.into())
                 .collect(),
-        }
+        })
     }
     fn part1(&self) -> i32 {
         self.rucksacks
@@ -140,14 +142,16 @@ mod day03tests {
     }
 
     #[test]
-    fn part1() {
-        let day = AocDay03::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay03::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 157);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay03::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay03::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 70);
+        Ok(())
     }
 }
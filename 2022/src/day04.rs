@@ -1,16 +1,16 @@
-use std::{num::ParseIntError, str::FromStr, u8};
+use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{parsing::numbered_lines, AocDay, DayError};
 
 struct SectionPair(Section, Section);
 
 impl FromStr for SectionPair {
-    type Err = ParseIntError;
+    type Err = DayError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pair = s
-            .split_once(',')
-            .expect("Section pair must contain , delimiter");
+        let pair = s.split_once(',').ok_or(DayError::GenericParseErr(
+            "section pair must contain , delimiter",
+        ))?;
 
         Ok(SectionPair(pair.0.parse()?, pair.1.parse()?))
     }
@@ -28,10 +28,12 @@ impl SectionPair {
 struct Section(u128);
 
 impl FromStr for Section {
-    type Err = ParseIntError;
+    type Err = DayError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bounds = s.split_once('-').expect("Section must contain - delimiter");
+        let bounds = s.split_once('-').ok_or(DayError::GenericParseErr(
+            "section must contain - delimiter",
+        ))?;
 
         Ok(Self::from_range(bounds.0.parse()?, bounds.1.parse()?))
     }
@@ -67,14 +69,13 @@ pub struct AocDay04 {
 }
 
 impl AocDay<usize, usize> for AocDay04 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        AocDay04 {
-            section_pairs: lines
-                .map(|x| x.trim().to_string())
-                .filter(|x| !x.is_empty())
-                .map(|x| x.parse().unwrap())
-                .collect(),
-        }
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        Ok(AocDay04 {
+            section_pairs: numbered_lines(lines)
+                .filter(|line| !line.text.trim().is_empty())
+                .map(|line| line.parse_with(|text| text.trim().parse()))
+                .collect::<Result<_, _>>()?,
+        })
     }
     fn part1(&self) -> usize {
         self.section_pairs
@@ -99,14 +100,16 @@ mod day04tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay04::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay04::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 2);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay04::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay04::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 4);
+        Ok(())
     }
 }
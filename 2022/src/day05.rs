@@ -1,6 +1,6 @@
 use std::{collections::LinkedList, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 pub struct AocDay05 {
     cranes: Vec<Crane>,
@@ -15,31 +15,21 @@ struct Move {
     from: usize,
     to: usize,
 }
-struct MoveParseError(());
 impl FromStr for Move {
-    type Err = MoveParseError;
+    type Err = DayError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_ascii_whitespace().skip(1).step_by(2);
+        let mut next = || {
+            split
+                .next()
+                .ok_or(DayError::GenericParseErr("move is missing a number"))
+        };
 
         Ok(Move {
-            qnt: split
-                .next()
-                .ok_or(MoveParseError(()))?
-                .parse()
-                .map_err(|_| MoveParseError(()))?,
-            from: split
-                .next()
-                .ok_or(MoveParseError(()))?
-                .parse::<usize>()
-                .map_err(|_| MoveParseError(()))?
-                - 1,
-            to: split
-                .next()
-                .ok_or(MoveParseError(()))?
-                .parse::<usize>()
-                .map_err(|_| MoveParseError(()))?
-                - 1,
+            qnt: next()?.parse()?,
+            from: next()?.parse::<usize>()? - 1,
+            to: next()?.parse::<usize>()? - 1,
         })
     }
 }
@@ -76,11 +66,13 @@ fn top_level_crates_to_str(cranes: Vec<Crane>) -> String {
 }
 
 impl AocDay<String, String> for AocDay05 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut cranes: Vec<Crane> = Vec::new();
 
         loop {
-            let line = lines.next().expect("crane not finished");
+            let line = lines
+                .next()
+                .ok_or(DayError::GenericParseErr("crane not finished"))?;
             let crane_len = (line.len() + 1) / 4;
             if !line.contains('[') {
                 // finished cranes
@@ -100,9 +92,12 @@ impl AocDay<String, String> for AocDay05 {
             }
         }
 
-        let moves: Vec<Move> = lines.filter_map(|x| x.parse().ok()).collect();
+        let moves: Vec<Move> = lines
+            .filter(|x| !x.is_empty())
+            .map(|x| x.parse())
+            .collect::<Result<_, _>>()?;
 
-        AocDay05 { cranes, moves }
+        Ok(AocDay05 { cranes, moves })
     }
     fn part1(&self) -> String {
         let mut cranes = self.cranes.clone();
@@ -139,14 +134,16 @@ mod day05tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay05::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay05::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), "CMZ");
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay05::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay05::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), "MCD");
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::LinkedList;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 pub struct AocDay06 {
     datastream: String,
@@ -50,9 +50,11 @@ fn find_start_of_packet_start_index(size: usize, datastream: &String) -> usize {
 }
 
 impl AocDay<usize, usize> for AocDay06 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
-        let datastream = lines.next().expect("input must have at least a line");
-        AocDay06 { datastream }
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let datastream = lines
+            .next()
+            .ok_or(DayError::GenericParseErr("input must have at least a line"))?;
+        Ok(AocDay06 { datastream })
     }
     fn part1(&self) -> usize {
         find_start_of_packet_start_index(4, &self.datastream)
@@ -73,62 +75,72 @@ mod day06tests {
     const INPUT5: &[&str] = &["zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"];
 
     #[test]
-    fn part1_input1() {
-        let day = AocDay06::preprocessing(INPUT1.iter().map(|x| String::from(*x)));
+    fn part1_input1() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT1)?;
         assert_eq!(day.part1(), 7);
+        Ok(())
     }
 
     #[test]
-    fn part1_input2() {
-        let day = AocDay06::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+    fn part1_input2() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part1(), 5);
+        Ok(())
     }
 
     #[test]
-    fn part1_input3() {
-        let day = AocDay06::preprocessing(INPUT3.iter().map(|x| String::from(*x)));
+    fn part1_input3() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT3)?;
         assert_eq!(day.part1(), 6);
+        Ok(())
     }
 
     #[test]
-    fn part1_input4() {
-        let day = AocDay06::preprocessing(INPUT4.iter().map(|x| String::from(*x)));
+    fn part1_input4() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT4)?;
         assert_eq!(day.part1(), 10);
+        Ok(())
     }
 
     #[test]
-    fn part1_input5() {
-        let day = AocDay06::preprocessing(INPUT5.iter().map(|x| String::from(*x)));
+    fn part1_input5() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT5)?;
         assert_eq!(day.part1(), 11);
+        Ok(())
     }
 
     #[test]
-    fn part2_input1() {
-        let day = AocDay06::preprocessing(INPUT1.iter().map(|x| String::from(*x)));
+    fn part2_input1() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT1)?;
         assert_eq!(day.part2(), 19);
+        Ok(())
     }
 
     #[test]
-    fn part2_input2() {
-        let day = AocDay06::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+    fn part2_input2() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part2(), 23);
+        Ok(())
     }
 
     #[test]
-    fn part2_input3() {
-        let day = AocDay06::preprocessing(INPUT3.iter().map(|x| String::from(*x)));
+    fn part2_input3() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT3)?;
         assert_eq!(day.part2(), 23);
+        Ok(())
     }
 
     #[test]
-    fn part2_input4() {
-        let day = AocDay06::preprocessing(INPUT4.iter().map(|x| String::from(*x)));
+    fn part2_input4() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT4)?;
         assert_eq!(day.part2(), 29);
+        Ok(())
     }
 
     #[test]
-    fn part2_input5() {
-        let day = AocDay06::preprocessing(INPUT5.iter().map(|x| String::from(*x)));
+    fn part2_input5() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT5)?;
         assert_eq!(day.part2(), 26);
+        Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 pub struct AocDay07 {
     root: INode,
@@ -76,37 +76,45 @@ impl INode {
 fn preprocessing_recursive(
     curr_inode: &mut INode,
     mut lines: impl Iterator<Item = String>,
-) -> impl Iterator<Item = String> {
+) -> Result<impl Iterator<Item = String>, DayError> {
     while let Some(line) = lines.next() {
         let mut parts = line.split_ascii_whitespace();
-        match parts.next().expect("invalid input") {
-            "$" => match parts.next().expect("invalid dollar command") {
+        match parts
+            .next()
+            .ok_or(DayError::GenericParseErr("empty line in input"))?
+        {
+            "$" => match parts
+                .next()
+                .ok_or(DayError::GenericParseErr("missing dollar command"))?
+            {
                 "cd" => {
-                    let path = parts.next().expect("cd missing path");
+                    let path = parts
+                        .next()
+                        .ok_or(DayError::GenericParseErr("cd missing path"))?;
                     match path {
                         "/" => unreachable!("only the first line does this"),
                         ".." => {
-                            return lines;
+                            return Ok(lines);
                         }
                         _ => {
                             let new_inode = curr_inode.get_or_create_dir(path);
-                            lines = preprocessing_recursive(new_inode, lines);
+                            lines = preprocessing_recursive(new_inode, lines)?;
                         }
                     }
                 }
                 "ls" => {}
-                _ => unreachable!("not part of the problem input"),
+                _ => return Err(DayError::GenericParseErr("unknown dollar command")),
             },
             "dir" => {} // ignore empty dirs, just create them on cd
             size => {
                 // we can ignore file name, it's not used for anything
                 curr_inode.add_file(INode::File {
-                    size: size.parse().expect("size not a number"),
+                    size: size.parse()?,
                 });
             }
         }
     }
-    lines
+    Ok(lines)
 }
 
 // returns (directory_size, accumulated_size)
@@ -157,17 +165,17 @@ fn find_smallest_big_enough_size(
 }
 
 impl AocDay<usize, usize> for AocDay07 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut root = INode::Directory {
             name: String::new(),
             children: Vec::new(),
         };
 
-        lines.next().unwrap(); // ignore $ cd /
-        let mut lines = preprocessing_recursive(&mut root, lines);
+        lines.next(); // ignore $ cd /
+        let mut lines = preprocessing_recursive(&mut root, lines)?;
         assert!(lines.next().is_none());
 
-        AocDay07 { root }
+        Ok(AocDay07 { root })
     }
 
     fn part1(&self) -> usize {
@@ -215,14 +223,16 @@ mod day07tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay07::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay07::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 95437);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay07::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay07::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 24933642);
+        Ok(())
     }
 }
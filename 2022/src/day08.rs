@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(Debug)]
 struct Forest(Vec<u8>, usize);
@@ -78,9 +78,12 @@ pub struct AocDay08 {
 }
 
 impl AocDay<usize, usize> for AocDay08 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut lines = lines.peekable();
-        let side = lines.peek().expect("input must have one line").len();
+        let side = lines
+            .peek()
+            .ok_or(DayError::GenericParseErr("input must have one line"))?
+            .len();
         let mut forest = Forest::new(side);
 
         lines
@@ -89,7 +92,7 @@ impl AocDay<usize, usize> for AocDay08 {
 
         assert_eq!(forest.0.len(), forest.1 * forest.1);
 
-        AocDay08 { forest }
+        Ok(AocDay08 { forest })
     }
     fn part1(&self) -> usize {
         let mut visible_trees = HashSet::new();
@@ -117,14 +120,16 @@ mod day08tests {
     const INPUT: &[&str] = &["30373", "25512", "65332", "33549", "35390"];
 
     #[test]
-    fn part1() {
-        let day = AocDay08::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay08::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 21);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay08::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay08::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 8);
+        Ok(())
     }
 }
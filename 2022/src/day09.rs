@@ -1,6 +1,6 @@
-use std::{collections::HashSet, num::ParseIntError, str::FromStr};
+use std::{collections::HashSet, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 type Pos = (i16, i16);
 
@@ -8,15 +8,14 @@ type Pos = (i16, i16);
 struct Movement(Direction, u8);
 
 impl FromStr for Movement {
-    type Err = ParseIntError;
+    type Err = DayError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (direction, step) = s.split_once(' ').expect("malformatted input");
+        let (direction, step) = s
+            .split_once(' ')
+            .ok_or(DayError::GenericParseErr("malformatted movement"))?;
 
-        Ok(Movement(
-            direction.parse().expect("invalid direction"),
-            step.parse()?,
-        ))
+        Ok(Movement(direction.parse()?, step.parse()?))
     }
 }
 
@@ -29,14 +28,14 @@ enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = String;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "L" => Ok(Self::Left),
             "R" => Ok(Self::Right),
             "U" => Ok(Self::Up),
             "D" => Ok(Self::Down),
-            _ => Err("Unknown direction".to_string()),
+            _ => Err(DayError::GenericParseErr("unknown direction")),
         }
     }
 }
@@ -147,10 +146,10 @@ pub struct AocDay09 {
 }
 
 impl AocDay<usize, usize> for AocDay09 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let movements = lines.map(|x| x.parse().expect("invalid input")).collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let movements = parse_lines(lines)?;
 
-        AocDay09 { movements }
+        Ok(AocDay09 { movements })
     }
     fn part1(&self) -> usize {
         simulate_bridge::<1>(&self.movements)
@@ -168,20 +167,23 @@ mod day09tests {
     const INPUT2: &[&str] = &["R 5", "U 8", "L 8", "D 3", "R 17", "D 10", "L 25", "U 20"];
 
     #[test]
-    fn part1() {
-        let day = AocDay09::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay09::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 13);
+        Ok(())
     }
 
     #[test]
-    fn part2_small() {
-        let day = AocDay09::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2_small() -> Result<(), DayError> {
+        let day = AocDay09::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 1);
+        Ok(())
     }
 
     #[test]
-    fn part2_big() {
-        let day = AocDay09::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+    fn part2_big() -> Result<(), DayError> {
+        let day = AocDay09::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part2(), 36);
+        Ok(())
     }
 }
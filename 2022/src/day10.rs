@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError, LetterGrid};
 
 enum OpCode {
     NoOp,
@@ -8,33 +8,32 @@ enum OpCode {
 }
 
 impl FromStr for OpCode {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_ascii_whitespace();
-        match split.next().ok_or(ParseErr)? {
+        match split
+            .next()
+            .ok_or(DayError::GenericParseErr("empty instruction"))?
+        {
             "noop" => Ok(Self::NoOp),
             "addx" => Ok(Self::Addx(
                 split
                     .next()
-                    .ok_or(ParseErr)?
-                    .parse()
-                    .map_err(|_| ParseErr)?,
+                    .ok_or(DayError::GenericParseErr("addx is missing its argument"))?
+                    .parse()?,
             )),
-            _ => Err(ParseErr),
+            _ => Err(DayError::GenericParseErr("unknown instruction")),
         }
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay10 {
     strength: i32,
-    image: String,
+    image: LetterGrid,
 }
 
 fn is_strength_cycle(cycle: usize) -> bool {
-    (cycle + 20) % 40 == 0 && cycle <= 230
+    (cycle + 20).is_multiple_of(40) && cycle <= 230
 }
 
 fn get_display_char(cycle: usize, register: i32) -> char {
@@ -47,39 +46,35 @@ fn get_display_char(cycle: usize, register: i32) -> char {
     }
 }
 
-impl AocDay<i32, String> for AocDay10 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let (_, strength, _, image) = lines
-            .map(|x| x.parse::<OpCode>().expect("invalid input"))
-            .fold(
-                (1i32, 0i32, 1usize, Vec::new()),
-                |(register, mut strength, cycle, mut image), opcode| {
-                    if is_strength_cycle(cycle) {
-                        strength += cycle as i32 * register;
-                    }
-                    image.push(get_display_char(cycle, register));
-                    match opcode {
-                        OpCode::NoOp => (register, strength, cycle + 1, image),
-                        OpCode::Addx(x) => {
-                            if is_strength_cycle(cycle + 1) {
-                                strength += (cycle + 1) as i32 * register;
-                            }
-                            image.push(get_display_char(cycle + 1, register));
-                            (register + x, strength, cycle + 2, image)
+impl AocDay<i32, LetterGrid> for AocDay10 {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let (_, strength, _, image) = parse_lines::<OpCode>(lines)?.into_iter().fold(
+            (1i32, 0i32, 1usize, Vec::new()),
+            |(register, mut strength, cycle, mut image), opcode| {
+                if is_strength_cycle(cycle) {
+                    strength += cycle as i32 * register;
+                }
+                image.push(get_display_char(cycle, register));
+                match opcode {
+                    OpCode::NoOp => (register, strength, cycle + 1, image),
+                    OpCode::Addx(x) => {
+                        if is_strength_cycle(cycle + 1) {
+                            strength += (cycle + 1) as i32 * register;
                         }
+                        image.push(get_display_char(cycle + 1, register));
+                        (register + x, strength, cycle + 2, image)
                     }
-                },
-            );
+                }
+            },
+        );
 
-        let image = image.chunks(40).take(6).fold(String::new(), |acc, x| {
-            acc + "\n" + &String::from_iter(x.iter())
-        });
-        AocDay10 { strength, image }
+        let image = LetterGrid::from_rows(image.chunks(40).take(6).map(String::from_iter));
+        Ok(AocDay10 { strength, image })
     }
     fn part1(&self) -> i32 {
         self.strength
     }
-    fn part2(&self) -> String {
+    fn part2(&self) -> LetterGrid {
         self.image.clone()
     }
 }
@@ -91,24 +86,26 @@ mod day10tests {
     const INPUT: &str = include_str!("../inputs/day10_example.txt");
 
     #[test]
-    fn part1() {
-        let day = AocDay10::preprocessing(INPUT.lines().map(String::from));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay10::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(day.part1(), 13140);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay10::preprocessing(INPUT.lines().map(String::from));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay10::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(
             day.part2(),
-            concat!(
-                "\n##  ##  ##  ##  ##  ##  ##  ##  ##  ##  ",
-                "\n###   ###   ###   ###   ###   ###   ### ",
-                "\n####    ####    ####    ####    ####    ",
-                "\n#####     #####     #####     #####     ",
-                "\n######      ######      ######      ####",
-                "\n#######       #######       #######     "
-            )
+            LetterGrid::from_rows([
+                "##  ##  ##  ##  ##  ##  ##  ##  ##  ##  ",
+                "###   ###   ###   ###   ###   ###   ### ",
+                "####    ####    ####    ####    ####    ",
+                "#####     #####     #####     #####     ",
+                "######      ######      ######      ####",
+                "#######       #######       #######     ",
+            ])
         );
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 use itertools::Itertools;
 
@@ -11,15 +11,17 @@ enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (op, arg) = s.split_once(' ').ok_or(ParseErr)?;
+        let (op, arg) = s.split_once(' ').ok_or(DayError::GenericParseErr(
+            "operation is missing its argument",
+        ))?;
 
         let arg: OperationValue = arg.parse()?;
         match op {
             "+" => Ok(Self::Add(arg)),
             "*" => Ok(Self::Multiply(arg)),
-            _ => Err(ParseErr),
+            _ => Err(DayError::GenericParseErr("unknown operator")),
         }
     }
 }
@@ -40,11 +42,11 @@ enum OperationValue {
 }
 
 impl FromStr for OperationValue {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "old" => Ok(Self::Old),
-            _ => Ok(Self::Constant(s.parse().map_err(|_| ParseErr)?)),
+            _ => Ok(Self::Constant(s.parse()?)),
         }
     }
 }
@@ -58,9 +60,6 @@ impl OperationValue {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 #[derive(Debug, Clone)]
 struct Monkey {
     items: Vec<i64>,
@@ -128,46 +127,43 @@ pub struct AocDay11 {
 }
 
 impl AocDay<usize, usize> for AocDay11 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         // lines.array_chunks is still on nightly only :/
         // using itertools instead
         let monkeys: Vec<Monkey> = lines
             .chunks(7)
             .into_iter()
             .map(|mut iter| {
-                let starting_items: Vec<i64> = iter.nth(1).unwrap()[18..]
+                let mut next_line = |skip| {
+                    iter.nth(skip).ok_or(DayError::GenericParseErr(
+                        "monkey description is incomplete",
+                    ))
+                };
+                let starting_items: Vec<i64> = next_line(1)?[18..]
                     .split(", ")
-                    .map(|x| x.parse().expect("starting items must be integers"))
-                    .collect();
-                let operation: Operation = iter.next().unwrap()[23..]
-                    .parse()
-                    .expect("invalid operation");
-                let divisible_by: i64 = iter.next().unwrap()[21..]
-                    .parse()
-                    .expect("divisible by must be a number");
-                let monkey_if_true: usize = iter.next().unwrap()[29..]
-                    .parse()
-                    .expect("target monkey must be a number");
-                let monkey_if_false: usize = iter.next().unwrap()[30..]
-                    .parse()
-                    .expect("target monkey must be a number");
-                Monkey {
+                    .map(|x| x.parse())
+                    .collect::<Result<_, _>>()?;
+                let operation: Operation = next_line(0)?[23..].parse()?;
+                let divisible_by: i64 = next_line(0)?[21..].parse()?;
+                let monkey_if_true: usize = next_line(0)?[29..].parse()?;
+                let monkey_if_false: usize = next_line(0)?[30..].parse()?;
+                Ok(Monkey {
                     items: starting_items,
                     inspected_count: 0,
                     operation,
                     divisible_by,
                     monkey_if_true,
                     monkey_if_false,
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, DayError>>()?;
 
         let mult_divisible_by = monkeys.iter().map(|m| m.divisible_by).product();
 
-        AocDay11 {
+        Ok(AocDay11 {
             monkeys,
             mult_divisible_by,
-        }
+        })
     }
     fn part1(&self) -> usize {
         let mut monkeys: Vec<Monkey> = self.monkeys.to_vec();
@@ -194,14 +190,16 @@ mod day11tests {
     const INPUT: &str = include_str!("../inputs/day11_example.txt");
 
     #[test]
-    fn part1() {
-        let day = AocDay11::preprocessing(INPUT.lines().map(String::from));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay11::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(day.part1(), 10605);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay11::preprocessing(INPUT.lines().map(String::from));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay11::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(day.part2(), 2713310158);
+        Ok(())
     }
 }
@@ -1,14 +1,12 @@
+use aoc_common::{search::bfs, AocDay, DayError};
 #[cfg(debug_assertions)]
 use colored::Colorize;
-use pathfinding::prelude::astar;
-
-use crate::AocDay;
 
 type Pos = (usize, usize);
 type Map = Vec<Vec<u8>>;
 
 // we're finding the path backwards, so the rules are inverted
-fn get_possible_next_positions(map: &Map, pos: &Pos) -> Vec<(Pos, i32)> {
+fn get_possible_next_positions(map: &Map, pos: &Pos) -> Vec<Pos> {
     let height = map.get(pos.1).and_then(|row| row.get(pos.0));
     if height.is_none() {
         return Vec::new();
@@ -23,15 +21,11 @@ fn get_possible_next_positions(map: &Map, pos: &Pos) -> Vec<(Pos, i32)> {
             map.get(new_pos.1)
                 .and_then(|row| row.get(new_pos.0))
                 .filter(|x| **x + 1 >= height)
-                .map(|_| (new_pos, 1))
+                .map(|_| new_pos)
         })
         .collect()
 }
 
-fn heuristic_fun(pos: &Pos, target_pos: &Pos) -> i32 {
-    (pos.0 as i32 - target_pos.0 as i32).abs() + (pos.1 as i32 - target_pos.1 as i32).abs()
-}
-
 #[derive(Debug)]
 pub struct AocDay12 {
     map: Map,
@@ -66,7 +60,7 @@ impl AocDay12 {
 }
 
 impl AocDay<i32, i32> for AocDay12 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut start = None;
         let mut end = None;
         let map = lines
@@ -89,39 +83,37 @@ impl AocDay<i32, i32> for AocDay12 {
             })
             .collect();
 
-        AocDay12 {
+        Ok(AocDay12 {
             map,
-            start: start.unwrap(),
-            end: end.unwrap(),
-        }
+            start: start.ok_or(DayError::GenericParseErr("map has no start position"))?,
+            end: end.ok_or(DayError::GenericParseErr("map has no end position"))?,
+        })
     }
     fn part1(&self) -> i32 {
-        let (_path, steps) = astar(
-            &self.end,
+        let path = bfs(
+            [self.end],
             |pos| get_possible_next_positions(&self.map, pos),
-            |pos| heuristic_fun(pos, &self.start),
             |pos| *pos == self.start,
         )
         .expect("no goal reached");
 
         #[cfg(debug_assertions)]
-        self.print_map(&_path);
+        self.print_map(&path);
 
-        steps
+        path.len() as i32 - 1
     }
     fn part2(&self) -> i32 {
-        let (_path, steps) = astar(
-            &self.end,
+        let path = bfs(
+            [self.end],
             |pos| get_possible_next_positions(&self.map, pos),
-            |pos| self.get_height(pos).unwrap_or(0) as i32,
             |pos| self.get_height(pos).filter(|x| *x == 0).is_some(),
         )
         .expect("no goal reached");
 
         #[cfg(debug_assertions)]
-        self.print_map(&_path);
+        self.print_map(&path);
 
-        steps
+        path.len() as i32 - 1
     }
 }
 
@@ -132,14 +124,16 @@ mod day12tests {
     const INPUT: &[&str] = &["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"];
 
     #[test]
-    fn part1() {
-        let day = AocDay12::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay12::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 31);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay12::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay12::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 29);
+        Ok(())
     }
 }
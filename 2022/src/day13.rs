@@ -2,7 +2,7 @@ use std::{cmp::Ordering, str::FromStr};
 
 use itertools::{EitherOrBoth, Itertools};
 
-use crate::AocDay;
+use aoc_common::{parsing::numbered_lines, AocDay, DayError};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum PacketElement {
@@ -30,10 +30,10 @@ impl PacketElement {
 }
 
 impl FromStr for PacketElement {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.chars().next().ok_or(ParseErr)? != '[' {
-            return Ok(Self::Constant(s.parse().map_err(|_| ParseErr)?));
+        if !s.starts_with('[') {
+            return Ok(Self::Constant(s.parse()?));
         }
         if s.len() == 2 {
             return Ok(Self::List(Vec::new()));
@@ -88,18 +88,18 @@ impl Ord for PacketElement {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay13 {
     packets: Vec<PacketElement>,
 }
 
 impl AocDay<usize, usize> for AocDay13 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let packets = lines.filter_map(|line| line.parse().ok()).collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let packets = numbered_lines(lines)
+            .filter(|line| !line.text.is_empty())
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
 
-        AocDay13 { packets }
+        Ok(AocDay13 { packets })
     }
     fn part1(&self) -> usize {
         self.packets
@@ -160,14 +160,16 @@ mod day13tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay13::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay13::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 13);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay13::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay13::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 140);
+        Ok(())
     }
 }
@@ -1,46 +1,30 @@
-use std::{collections::HashSet, hash::Hash, str::FromStr};
+use aoc_common::{navigation::Vec2D, sparse_grid::SparseGrid, AocDay, DayError};
 
-use crate::AocDay;
+type Pos = Vec2D<isize>;
 
-#[derive(Debug, Hash, Eq, PartialEq, Clone)]
-struct Pos(i32, i32);
+fn parse_pos(s: &str) -> Result<Pos, DayError> {
+    let (x, y) = s.split_once(',').ok_or(DayError::GenericParseErr(
+        "position must contain , delimiter",
+    ))?;
 
-impl Pos {
-    fn sum_vec(&self, vec: (i32, i32)) -> Self {
-        Self(self.0 + vec.0, self.1 + vec.1)
-    }
+    Ok(Pos::new(x.parse()?, y.parse()?))
 }
 
-impl FromStr for Pos {
-    type Err = ParseErr;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(ParseErr)?;
-
-        Ok(Self(
-            x.parse().map_err(|_| ParseErr)?,
-            y.parse().map_err(|_| ParseErr)?,
-        ))
-    }
-}
-
-#[derive(Debug)]
-struct ParseErr;
-
 #[derive(Clone)]
 struct Cave {
-    occupied: HashSet<Pos>,
-    lowest_rock: i32,
+    occupied: SparseGrid<()>,
+    lowest_rock: isize,
 }
 
 impl Cave {
     fn new() -> Self {
         Self {
-            occupied: HashSet::new(),
+            occupied: SparseGrid::new(),
             lowest_rock: 0,
         }
     }
 
-    fn range(start: i32, end: i32) -> impl Iterator<Item = i32> {
+    fn range(start: isize, end: isize) -> impl Iterator<Item = isize> {
         if start < end {
             start..=end
         } else {
@@ -49,38 +33,37 @@ impl Cave {
     }
 
     fn draw_line(&mut self, start: &Pos, end: &Pos) {
-        for x in Self::range(start.0, end.0) {
-            for y in Self::range(start.1, end.1) {
-                self.occupied.insert(Pos(x, y));
+        for x in Self::range(start.x, end.x) {
+            for y in Self::range(start.y, end.y) {
+                self.occupied.insert(Pos::new(x, y), ());
             }
         }
-        self.lowest_rock = self.lowest_rock.max(start.1).max(end.1);
     }
 
     fn simulate_sand_falling<const FLOOR: bool>(&mut self) -> bool {
-        let mut sand_pos = Pos(500, 0);
+        let mut sand_pos = Pos::new(500, 0);
 
         let stop_height = match FLOOR {
             true => self.lowest_rock + 1,
             false => self.lowest_rock,
         };
-        while sand_pos.1 < stop_height {
+        while sand_pos.y < stop_height {
             let next_pos = [(0, 1), (-1, 1), (1, 1)]
-                .iter()
-                .map(|&vec| sand_pos.sum_vec(vec))
+                .into_iter()
+                .map(|(x, y)| &sand_pos + &Pos::new(x, y))
                 .find(|pos| !self.occupied.contains(pos));
 
             match next_pos {
                 Some(next_pos) => sand_pos = next_pos,
                 None => {
-                    self.occupied.insert(sand_pos);
+                    self.occupied.insert(sand_pos, ());
                     return true;
                 }
             }
         }
 
         if FLOOR {
-            self.occupied.insert(sand_pos);
+            self.occupied.insert(sand_pos, ());
         }
         false
     }
@@ -91,23 +74,24 @@ pub struct AocDay14 {
 }
 
 impl AocDay<i32, i32> for AocDay14 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut cave = Cave::new();
 
         for line in lines {
             let points = line
                 .split(" -> ")
-                .map(|x| x.parse())
-                .collect::<Result<Vec<Pos>, _>>()
-                .expect("Failed to parse line");
+                .map(parse_pos)
+                .collect::<Result<Vec<_>, _>>()?;
 
             points
                 .iter()
                 .zip(points.iter().skip(1))
                 .for_each(|(start, end)| cave.draw_line(start, end));
         }
+        // only rocks are in the cave yet
+        cave.lowest_rock = cave.occupied.bounds().map_or(0, |(_, max)| max.y);
 
-        AocDay14 { cave }
+        Ok(AocDay14 { cave })
     }
     fn part1(&self) -> i32 {
         let mut cave = self.cave.clone();
@@ -119,7 +103,7 @@ impl AocDay<i32, i32> for AocDay14 {
     }
     fn part2(&self) -> i32 {
         let mut cave = self.cave.clone();
-        let target_pos = Pos(500, 0);
+        let target_pos = Pos::new(500, 0);
         let mut i = 0;
         while !cave.occupied.contains(&target_pos) {
             cave.simulate_sand_falling::<true>();
@@ -139,14 +123,16 @@ mod day14tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay14::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay14::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 24);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay14::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay14::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 93);
+        Ok(())
     }
 }
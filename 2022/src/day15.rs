@@ -1,9 +1,6 @@
-use std::{
-    collections::{BTreeSet, HashSet},
-    str::FromStr,
-};
+use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{interval_set::IntervalSet, parsing::parse_lines, AocDay, DayError};
 
 #[cfg(test)]
 const TARGET_Y: i32 = 10;
@@ -17,32 +14,6 @@ const MAX_Y: i32 = 4000000;
 
 type Pos = (i32, i32);
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord)]
-struct Range1D {
-    start: i32,
-    end: i32,
-}
-
-impl Range1D {
-    fn new(start: i32, end: i32) -> Range1D {
-        Range1D { start, end }
-    }
-
-    fn len(&self) -> i32 {
-        (self.end - self.start + 1).max(0)
-    }
-
-    // Remove "other" from "self"
-    fn subtract(&self, other: &Range1D) -> impl Iterator<Item = Range1D> {
-        [
-            Range1D::new(self.start, self.end.min(other.start - 1)),
-            Range1D::new(self.start.max(other.end + 1), self.end),
-        ]
-        .into_iter()
-        .filter(|range| range.len() > 0)
-    }
-}
-
 #[derive(Debug)]
 struct Sensor {
     pos: Pos,
@@ -51,40 +22,41 @@ struct Sensor {
 }
 
 impl Sensor {
-    fn parse_coord<const N: usize>(string: &str) -> Result<i32, ParseErr> {
-        string
+    fn parse_coord<const N: usize>(string: &str) -> Result<i32, DayError> {
+        Ok(string
             .get(2..(string.len() - N))
-            .ok_or(ParseErr)?
-            .parse()
-            .map_err(|_| ParseErr)
+            .ok_or(DayError::GenericParseErr("coordinate is too short"))?
+            .parse()?)
     }
 
-    fn get_1d_range_for_y(&self, y: i32) -> Option<Range1D> {
+    fn get_1d_range_for_y(&self, y: i32) -> Option<RangeInclusive<i32>> {
         let distance_to_y = (self.pos.1 - y).abs();
 
         let distance_left = self.distance - distance_to_y;
         if distance_left < 0 {
             None
         } else {
-            Some(Range1D::new(
-                self.pos.0 - distance_left,
-                self.pos.0 + distance_left,
-            ))
+            Some((self.pos.0 - distance_left)..=(self.pos.0 + distance_left))
         }
     }
 }
 
 impl FromStr for Sensor {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_ascii_whitespace();
+        let mut nth_word = |n| {
+            split.nth(n).ok_or(DayError::GenericParseErr(
+                "sensor description is incomplete",
+            ))
+        };
         let pos: Pos = (
-            Self::parse_coord::<1>(split.nth(2).ok_or(ParseErr)?)?,
-            Self::parse_coord::<1>(split.next().ok_or(ParseErr)?)?,
+            Self::parse_coord::<1>(nth_word(2)?)?,
+            Self::parse_coord::<1>(nth_word(0)?)?,
         );
         let closest_beacon: Pos = (
-            Self::parse_coord::<1>(split.nth(4).ok_or(ParseErr)?)?,
-            Self::parse_coord::<0>(split.next().ok_or(ParseErr)?)?,
+            Self::parse_coord::<1>(nth_word(4)?)?,
+            Self::parse_coord::<0>(nth_word(0)?)?,
         );
 
         Ok(Sensor {
@@ -95,9 +67,6 @@ impl FromStr for Sensor {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 fn manhattan_distance(pos1: &Pos, pos2: &Pos) -> i32 {
     (pos1.0 - pos2.0).abs() + (pos1.1 - pos2.1).abs()
 }
@@ -107,54 +76,19 @@ pub struct AocDay15 {
 }
 
 impl AocDay15 {
-    fn ranges_in_row(&self, target_y: i32) -> BTreeSet<Range1D> {
-        let mut ranges: BTreeSet<Range1D> = BTreeSet::new();
-
-        for sensor in self.sensors.iter() {
-            let range = sensor.get_1d_range_for_y(target_y);
-            if range.is_none() {
-                continue;
-            }
-            ranges
-                .iter()
-                .fold(vec![range.unwrap().clone()], |r1, r2| {
-                    r1.iter().flat_map(|r| r.subtract(r2)).collect()
-                })
-                .into_iter()
-                .for_each(|r| {
-                    ranges.insert(r);
-                });
-        }
-
-        ranges
-    }
-
-    fn is_sequential(ranges: &BTreeSet<Range1D>) -> bool {
-        ranges
+    fn ranges_in_row(&self, target_y: i32) -> IntervalSet<i32> {
+        self.sensors
             .iter()
-            .zip(ranges.iter().skip(1))
-            .all(|(r1, r2)| r1.end + 1 == r2.start)
-    }
-
-    fn find_hole(ranges: &BTreeSet<Range1D>) -> Option<i32> {
-        ranges
-            .iter()
-            .zip(ranges.iter().skip(1))
-            .find_map(|(r1, r2)| match r1.end + 1 - r2.start {
-                0 => None,
-                _ => Some(r1.end + 1),
-            })
+            .filter_map(|sensor| sensor.get_1d_range_for_y(target_y))
+            .collect()
     }
 }
 
 impl AocDay<i32, i128> for AocDay15 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let sensors = lines
-            .map(|line| line.parse())
-            .collect::<Result<Vec<_>, _>>()
-            .expect("failed to parse input");
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let sensors = parse_lines(lines)?;
 
-        AocDay15 { sensors }
+        Ok(AocDay15 { sensors })
     }
     fn part1(&self) -> i32 {
         let ranges = self.ranges_in_row(TARGET_Y);
@@ -165,20 +99,21 @@ impl AocDay<i32, i128> for AocDay15 {
             .filter(|beacon| beacon.1 == TARGET_Y)
             .collect();
 
-        ranges.iter().map(Range1D::len).sum::<i32>() - beacons_in_row.len() as i32
+        ranges.len() as i32 - beacons_in_row.len() as i32
     }
 
     fn part2(&self) -> i128 {
-        let ranges = (0..=MAX_Y)
-            .map(|y| (y, self.ranges_in_row(y)))
-            .find(|(_, ranges)| !Self::is_sequential(ranges));
-
-        let (y, ranges) = ranges.expect("no non-sequential row found");
-
-        let x: i128 =
-            Self::find_hole(&ranges).expect("non-sequential row does not have a hole") as i128;
+        let search_area = IntervalSet::from(0..=MAX_Y);
+        let (x, y) = (0..=MAX_Y)
+            .find_map(|y| {
+                search_area
+                    .difference(&self.ranges_in_row(y))
+                    .min()
+                    .map(|x| (x, y))
+            })
+            .expect("no row with a hole found");
 
-        x * 4000000 + y as i128
+        x as i128 * 4000000 + y as i128
     }
 }
 
@@ -204,25 +139,16 @@ mod day15tests {
     ];
 
     #[test]
-    fn range_subtract() {
-        let range1 = Range1D::new(2, 2);
-        let range2 = Range1D::new(12, 12);
-
-        assert_eq!(
-            range1.subtract(&range2).collect::<Vec<_>>(),
-            vec![Range1D::new(2, 2)]
-        );
-    }
-
-    #[test]
-    fn part1() {
-        let day = AocDay15::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay15::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 26);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay15::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay15::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 56000011);
+        Ok(())
     }
 }
@@ -3,7 +3,7 @@ use std::{
     collections::{HashMap, VecDeque},
 };
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 type Edges = Vec<String>;
 type AllEdges = HashMap<String, Edges>;
@@ -172,42 +172,45 @@ pub struct AocDay16 {
 }
 
 impl AocDay<u32, u32> for AocDay16 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut edges = HashMap::new();
         let mut flow_rates = HashMap::new();
 
-        lines.for_each(|line| {
+        for line in lines {
             let mut parts = line.split_ascii_whitespace();
-            let valve_name = parts.nth(1).expect("expected valve name on input");
+            let valve_name = parts
+                .nth(1)
+                .ok_or(DayError::GenericParseErr("expected valve name on input"))?;
 
-            let flow_rate = parts.nth(2).expect("expected flow rate on input");
+            let flow_rate = parts
+                .nth(2)
+                .ok_or(DayError::GenericParseErr("expected flow rate on input"))?;
             let flow_rate: u32 = flow_rate
                 .get(5..(flow_rate.len() - 1))
-                .expect("flow rate has unexpected size")
-                .parse()
-                .expect("flow rate is not an integer");
+                .ok_or(DayError::GenericParseErr("flow rate has unexpected size"))?
+                .parse()?;
 
             let valve_edges: Vec<String> = parts
                 .skip(4)
                 .map(|x| {
                     x.get(0..2)
                         .map(String::from)
-                        .expect("destination valve too small")
+                        .ok_or(DayError::GenericParseErr("destination valve too small"))
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
 
             edges.insert(String::from(valve_name), valve_edges);
             if flow_rate > 0 {
                 flow_rates.insert(String::from(valve_name), flow_rate);
             }
-        });
+        }
 
         let valves_distances: AllValveDistances = calculate_distances_to_nodes(&edges, &flow_rates);
 
-        AocDay16 {
+        Ok(AocDay16 {
             flow_rates,
             valves_distances,
-        }
+        })
     }
 
     fn part1(&self) -> u32 {
@@ -251,14 +254,16 @@ mod day16tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay16::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay16::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 1651);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay16::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay16::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 1707);
+        Ok(())
     }
 }
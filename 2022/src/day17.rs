@@ -1,10 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::AocDay;
+use aoc_common::{cycle, AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, TryFromChar)]
 enum Push {
+    #[char_repr = '<']
     Left,
+    #[char_repr = '>']
     Right,
 }
 
@@ -17,16 +20,6 @@ impl Push {
     }
 }
 
-impl From<char> for Push {
-    fn from(c: char) -> Self {
-        match c {
-            '>' => Self::Right,
-            '<' => Self::Left,
-            _ => unreachable!("invalid push type"),
-        }
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
 enum RockType {
     Horizontal,
@@ -95,99 +88,102 @@ struct CycleState {
     rock_type: RockType,
 }
 
-fn simulate_game(gas_rules: &[Push], mut n: i64) -> i64 {
-    let mut current_rock_type = RockType::Horizontal;
-    let mut heights = [0i64; 7];
-    let mut taken_pos = HashSet::new();
-    let mut cycle_mem = HashMap::new();
-
-    let mut gas_rules = gas_rules.iter().cloned().enumerate().cycle().peekable();
-
-    let mut height_modifier: i64 = 0;
+#[derive(Debug)]
+struct Chamber<'a> {
+    gas_rules: &'a [Push],
+    gas_rules_i: usize,
+    rock_type: RockType,
+    heights: [i64; 7],
+    taken_pos: HashSet<Pos>,
+}
 
-    while n > 0 {
-        simulate_rock(
-            &mut gas_rules,
-            current_rock_type.clone(),
-            &mut heights,
-            &mut taken_pos,
-        );
+impl<'a> Chamber<'a> {
+    fn new(gas_rules: &'a [Push]) -> Self {
+        Chamber {
+            gas_rules,
+            gas_rules_i: 0,
+            rock_type: RockType::Horizontal,
+            heights: [0; 7],
+            taken_pos: HashSet::new(),
+        }
+    }
 
-        current_rock_type = current_rock_type.get_next();
+    fn max_height(&self) -> i64 {
+        *self.heights.iter().max().unwrap()
+    }
 
-        let max_height = *heights.iter().max().unwrap();
-        let state = CycleState {
-            heights: heights.map(|h| max_height - h),
-            gas_rules_i: gas_rules.peek().unwrap().0,
-            rock_type: current_rock_type.clone(),
-        };
+    /// Everything the next rocks depend on, which only looks at the shape of
+    /// the top of the tower.
+    fn cycle_state(&self) -> CycleState {
+        let max_height = self.max_height();
+        CycleState {
+            heights: self.heights.map(|h| max_height - h),
+            gas_rules_i: self.gas_rules_i,
+            rock_type: self.rock_type.clone(),
+        }
+    }
 
-        n -= 1;
+    fn is_valid_position(&self, pos: &Pos, rock_type: &RockType) -> bool {
+        pos.0 >= 0
+            && pos.0 + rock_type.get_width() <= 7
+            && pos.1 - rock_type.get_height() >= 0
+            && rock_type
+                .get_positions(pos)
+                .iter()
+                .all(|p| !self.taken_pos.contains(p))
+    }
 
-        // Cycle detector
-        if let Some(old_state) = cycle_mem.insert(state, (max_height, n)) {
-            let height_period = max_height - old_state.0;
-            let rock_count_period = old_state.1 - n;
+    fn next_push(&mut self) -> Push {
+        let push = self.gas_rules[self.gas_rules_i].clone();
+        self.gas_rules_i = (self.gas_rules_i + 1) % self.gas_rules.len();
+        push
+    }
 
-            let rounds_to_skip = n / rock_count_period;
-            let rounds_left_to_sim = n % rock_count_period;
+    fn simulate_rock(&mut self) {
+        let rock_type = self.rock_type.clone();
+        self.rock_type = rock_type.get_next();
 
-            height_modifier = height_period * rounds_to_skip;
-            n = rounds_left_to_sim;
+        let start_y = self.max_height() + 3;
+        let mut rock = Rock {
+            pos: (2, start_y + rock_type.get_height()),
+            rock_type,
+        };
 
-            cycle_mem.clear();
+        loop {
+            // Simulate gas stream
+            let next_pos: Pos = self.next_push().apply(&rock.pos);
+            if self.is_valid_position(&next_pos, &rock.rock_type) {
+                rock.pos = next_pos;
+            }
+
+            // Simulate downwards movement
+            let next_pos: Pos = (rock.pos.0, rock.pos.1 - 1);
+            if self.is_valid_position(&next_pos, &rock.rock_type) {
+                rock.pos = next_pos;
+            } else {
+                // solidify
+                rock.rock_type
+                    .get_positions(&rock.pos)
+                    .into_iter()
+                    .for_each(|p| {
+                        let col_height = self.heights.get_mut(p.0 as usize).unwrap();
+                        *col_height = p.1.max(*col_height);
+                        self.taken_pos.insert(p);
+                    });
+                break;
+            }
         }
     }
-
-    *heights.iter().max().unwrap() + height_modifier
 }
 
-fn is_valid_position(pos: &Pos, rock_type: &RockType, taken_pos: &HashSet<Pos>) -> bool {
-    pos.0 >= 0
-        && pos.0 + rock_type.get_width() <= 7
-        && pos.1 - rock_type.get_height() >= 0
-        && rock_type
-            .get_positions(pos)
-            .iter()
-            .all(|p| !taken_pos.contains(p))
-}
-
-fn simulate_rock(
-    gas_rules: &mut impl Iterator<Item = (usize, Push)>,
-    rock_type: RockType,
-    heights: &mut [i64; 7],
-    taken_pos: &mut HashSet<Pos>,
-) {
-    let start_y = heights.iter().max().unwrap_or(&0) + 3;
-    let mut rock = Rock {
-        pos: (2, start_y + rock_type.get_height()),
-        rock_type,
-    };
-
-    loop {
-        // Simulate gas stream
-        let next_pos: Pos = gas_rules.next().unwrap().1.apply(&rock.pos);
-        if is_valid_position(&next_pos, &rock.rock_type, taken_pos) {
-            rock.pos = next_pos;
-        }
-
-        // Simulate downwards movement
-        let next_pos: Pos = (rock.pos.0, rock.pos.1 - 1);
-        if is_valid_position(&next_pos, &rock.rock_type, taken_pos) {
-            rock.pos = next_pos;
-        } else {
-            // solidify
-            rock.rock_type
-                .get_positions(&rock.pos)
-                .into_iter()
-                .for_each(|p| {
-                    let col_height = heights.get_mut(p.0 as usize).unwrap();
-                    *col_height = p.1.max(*col_height);
-                    taken_pos.insert(p);
-                });
-            break;
-        }
-    }
+fn simulate_game(gas_rules: &[Push], n: usize) -> i64 {
+    cycle::fast_forward(
+        Chamber::new(gas_rules),
+        n,
+        Chamber::simulate_rock,
+        Chamber::cycle_state,
+        Chamber::max_height,
+    )
 }
 
 pub struct AocDay17 {
@@ -195,15 +191,15 @@ pub struct AocDay17 {
 }
 
 impl AocDay<i64, i64> for AocDay17 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let gas_rules: Vec<Push> = lines
             .next()
-            .expect("Input must have one line")
+            .ok_or(DayError::GenericParseErr("input must have one line"))?
             .chars()
-            .map(|x| x.into())
-            .collect();
+            .map(|x| x.try_into())
+            .collect::<Result<_, _>>()?;
 
-        AocDay17 { gas_rules }
+        Ok(AocDay17 { gas_rules })
     }
     fn part1(&self) -> i64 {
         simulate_game(&self.gas_rules, 2022)
@@ -220,14 +216,16 @@ mod day17tests {
     const INPUT: &[&str] = &[">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"];
 
     #[test]
-    fn part1() {
-        let day = AocDay17::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay17::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 3068);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay17::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay17::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 1_514_285_714_288);
+        Ok(())
     }
 }
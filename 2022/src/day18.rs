@@ -1,53 +1,15 @@
-use std::{collections::HashSet, str::FromStr};
+use std::collections::HashSet;
 
 use pathfinding::directed::bfs;
 
-use crate::AocDay;
+use aoc_common::{navigation::Vec3D, parsing::numbered_lines, AocDay, DayError};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-struct Pos {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Pos {
-    fn get_adjacent(&self) -> [Pos; 6] {
-        [
-            (1, 0, 0),
-            (-1, 0, 0),
-            (0, 1, 0),
-            (0, -1, 0),
-            (0, 0, 1),
-            (0, 0, -1),
-        ]
-        .map(|p| Pos {
-            x: self.x + p.0,
-            y: self.y + p.1,
-            z: self.z + p.2,
-        })
-    }
-    fn is_inside_bounds(&self, max_bound: &Pos) -> bool {
-        self.x <= max_bound.x && self.y <= max_bound.y && self.z <= max_bound.z
-    }
-}
-
-impl FromStr for Pos {
-    type Err = ParseErr;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut it = s.split(',');
+type Pos = Vec3D<i32>;
 
-        Ok(Pos {
-            x: it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            y: it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            z: it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-        })
-    }
+fn is_inside_bounds(pos: &Pos, max_bound: &Pos) -> bool {
+    pos.x <= max_bound.x && pos.y <= max_bound.y && pos.z <= max_bound.z
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 fn is_outside(
     pos: &Pos,
     droplet_pos: &HashSet<Pos>,
@@ -58,10 +20,9 @@ fn is_outside(
     let res = bfs::bfs(
         pos,
         |p| {
-            p.get_adjacent()
-                .into_iter()
+            p.orthogonal_neighbours()
                 .filter(|x| !droplet_pos.contains(x))
-                .filter(|x| x.is_inside_bounds(max_pos)) // not needed, but improves speed
+                .filter(|x| is_inside_bounds(x, max_pos)) // not needed, but improves speed
                 .collect::<Vec<_>>()
         },
         |p| outside_pos.contains(p) || inside_pos.contains(p),
@@ -93,19 +54,18 @@ pub struct AocDay18 {
 }
 
 impl AocDay<usize, usize> for AocDay18 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let droplet = lines
-            .map(|x| x.parse().expect("failed to parse coordinate"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let droplet = numbered_lines(lines)
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
 
-        AocDay18 { droplet }
+        Ok(AocDay18 { droplet })
     }
     fn part1(&self) -> usize {
         self.droplet
             .iter()
             .map(|p| {
-                p.get_adjacent()
-                    .iter()
+                p.orthogonal_neighbours()
                     .filter(|adj| !self.droplet.contains(adj))
                     .count()
             })
@@ -133,8 +93,7 @@ impl AocDay<usize, usize> for AocDay18 {
         self.droplet
             .iter()
             .map(|p| {
-                p.get_adjacent()
-                    .iter()
+                p.orthogonal_neighbours()
                     .filter(|adj| !self.droplet.contains(adj))
                     .filter(|adj| {
                         is_outside(
@@ -161,14 +120,16 @@ mod day18tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay18::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay18::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 64);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay18::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay18::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 58);
+        Ok(())
     }
 }
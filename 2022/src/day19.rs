@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 #[derive(Debug)]
 struct Blueprint {
@@ -14,24 +14,28 @@ struct Blueprint {
 }
 
 impl FromStr for Blueprint {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut it = s.split_ascii_whitespace();
+        let mut nth_word = |n| {
+            it.nth(n).ok_or(DayError::GenericParseErr(
+                "blueprint description is incomplete",
+            ))
+        };
 
-        let id_str = it.nth(1).ok_or(ParseErr)?;
+        let id_str = nth_word(1)?;
 
         Ok(Blueprint {
             id: id_str
                 .get(0..(id_str.len() - 1))
-                .ok_or(ParseErr)?
-                .parse()
-                .map_err(|_| ParseErr)?,
-            ore_robot_cost: it.nth(4).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            clay_robot_cost: it.nth(5).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            obsidian_robot_cost_ore: it.nth(5).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            obsidian_robot_cost_clay: it.nth(2).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            geode_robot_cost_ore: it.nth(5).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
-            geode_robot_cost_obsidian: it.nth(2).ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?,
+                .ok_or(DayError::GenericParseErr("blueprint id is empty"))?
+                .parse()?,
+            ore_robot_cost: nth_word(4)?.parse()?,
+            clay_robot_cost: nth_word(5)?.parse()?,
+            obsidian_robot_cost_ore: nth_word(5)?.parse()?,
+            obsidian_robot_cost_clay: nth_word(2)?.parse()?,
+            geode_robot_cost_ore: nth_word(5)?.parse()?,
+            geode_robot_cost_obsidian: nth_word(2)?.parse()?,
         })
     }
 }
@@ -145,7 +149,7 @@ fn simulate_blueprint_iteration(
             let mut state = state.clone();
 
             if robot_to_build.is_none() {
-                options.iter().flatten().cloned().for_each(|t| match t {
+                options.iter().flatten().for_each(|t| match t {
                     ItemType::Ore => state.ignored_types[0] = true,
                     ItemType::Clay => state.ignored_types[1] = true,
                     ItemType::Obsidian => state.ignored_types[2] = true,
@@ -192,20 +196,15 @@ fn simulate_blueprint_iteration(
         .unwrap()
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay19 {
     blueprints: Vec<Blueprint>,
 }
 
 impl AocDay<u32, u32> for AocDay19 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let blueprints = lines
-            .map(|x| x.parse().expect("invalid blueprint"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let blueprints = parse_lines(lines)?;
 
-        AocDay19 { blueprints }
+        Ok(AocDay19 { blueprints })
     }
     fn part1(&self) -> u32 {
         self.blueprints
@@ -232,14 +231,16 @@ mod day19tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay19::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay19::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 33);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay19::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay19::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 56 * 62);
+        Ok(())
     }
 }
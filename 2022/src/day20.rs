@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::LinkedList};
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 const DECRYPTION_KEY: i64 = 811589153;
 const DECRYPTION_ROUNDS: usize = 10;
@@ -75,12 +75,10 @@ pub struct AocDay20 {
 }
 
 impl AocDay<i64, i64> for AocDay20 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let list = lines
-            .map(|l| l.parse().expect("invalid number on input"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let list = parse_lines(lines)?;
 
-        AocDay20 { list }
+        Ok(AocDay20 { list })
     }
     fn part1(&self) -> i64 {
         let mut circ_list = CircularList::new(&self.list);
@@ -151,14 +149,16 @@ mod day20tests {
     }
 
     #[test]
-    fn part1() {
-        let day = AocDay20::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay20::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 3);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay20::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay20::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 1623178306);
+        Ok(())
     }
 }
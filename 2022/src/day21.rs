@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(Debug, Clone)]
 enum Operator {
@@ -22,14 +22,14 @@ impl Operator {
 }
 
 impl FromStr for Operator {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "+" => Ok(Self::Add),
             "-" => Ok(Self::Subtract),
             "*" => Ok(Self::Multipy),
             "/" => Ok(Self::Divide),
-            _ => Err(ParseErr),
+            _ => Err(DayError::GenericParseErr("unknown operator")),
         }
     }
 }
@@ -41,15 +41,19 @@ enum Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value.parse::<i64>() {
             Ok(v) => Ok(Monkey::Value(v)),
             Err(_) => {
                 let mut it = value.split_ascii_whitespace();
-                let monkey1 = it.next().ok_or(ParseErr)?;
-                let op = it.next().ok_or(ParseErr)?.parse()?;
-                let monkey2 = it.next().ok_or(ParseErr)?;
+                let mut next_word = || {
+                    it.next()
+                        .ok_or(DayError::GenericParseErr("monkey recipe is incomplete"))
+                };
+                let monkey1 = next_word()?;
+                let op = next_word()?.parse()?;
+                let monkey2 = next_word()?;
 
                 Ok(Monkey::Recipe(op, monkey1.to_string(), monkey2.to_string()))
             }
@@ -57,9 +61,6 @@ impl FromStr for Monkey {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 fn calculate_monkey_value(monkeys: &mut HashMap<String, Monkey>, monkey: &str) -> i64 {
     match monkeys.get(monkey) {
         Some(Monkey::Value(v)) => *v,
@@ -93,16 +94,18 @@ pub struct AocDay21 {
 }
 
 impl AocDay<i64, i64> for AocDay21 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let monkeys = lines
             .map(|line| {
-                let (name, value) = line.split_once(": ").expect("invalid monkey");
+                let (name, value) = line
+                    .split_once(": ")
+                    .ok_or(DayError::GenericParseErr("monkey must contain : delimiter"))?;
 
-                (name.to_string(), value.parse().expect("invalid monkey"))
+                Ok((name.to_string(), value.parse()?))
             })
-            .collect();
+            .collect::<Result<_, DayError>>()?;
 
-        AocDay21 { monkeys }
+        Ok(AocDay21 { monkeys })
     }
     fn part1(&self) -> i64 {
         let mut monkeys = self.monkeys.clone();
@@ -163,14 +166,16 @@ mod day21tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay21::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay21::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 152);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay21::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay21::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 301);
+        Ok(())
     }
 }
@@ -1,211 +1,134 @@
-use std::collections::{HashMap, HashSet};
-
-use crate::AocDay;
-
-#[derive(Hash, PartialEq, Eq, Debug, Clone)]
-struct Pos(i32, i32);
-
-impl Pos {
-    fn get_clearance_pos(&self, direction: &Direction) -> [Pos; 3] {
-        // first position is the position to move it
-        match *direction {
-            Direction::North => [
-                Pos(self.0, self.1 - 1),
-                Pos(self.0 - 1, self.1 - 1),
-                Pos(self.0 + 1, self.1 - 1),
-            ],
-            Direction::East => [
-                Pos(self.0 + 1, self.1),
-                Pos(self.0 + 1, self.1 - 1),
-                Pos(self.0 + 1, self.1 + 1),
-            ],
-            Direction::South => [
-                Pos(self.0, self.1 + 1),
-                Pos(self.0 - 1, self.1 + 1),
-                Pos(self.0 + 1, self.1 + 1),
-            ],
-            Direction::West => [
-                Pos(self.0 - 1, self.1),
-                Pos(self.0 - 1, self.1 - 1),
-                Pos(self.0 - 1, self.1 + 1),
-            ],
-        }
-    }
-
-    fn get_adjacent_positions(&self) -> [Pos; 8] {
-        [
-            Pos(self.0 - 1, self.1 - 1),
-            Pos(self.0 - 1, self.1),
-            Pos(self.0 - 1, self.1 + 1),
-            Pos(self.0 + 1, self.1 - 1),
-            Pos(self.0 + 1, self.1),
-            Pos(self.0 + 1, self.1 + 1),
-            Pos(self.0, self.1 - 1),
-            Pos(self.0, self.1 + 1),
-        ]
-    }
+use aoc_common::{
+    automaton::{Neighbours, SparseAutomaton},
+    navigation::{Direction, DirectionSet, Vec2D},
+    sparse_grid::SparseGrid,
+    AocDay, DayError,
+};
+
+type Pos = Vec2D<isize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tile {
+    /// An elf, with the direction it proposes to move toward.
+    Elf(Option<Direction>),
+    /// Empty tile that some elves propose to move onto.
+    Proposed(usize),
 }
 
-#[derive(Clone)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
+type Elves = SparseAutomaton<Tile>;
 
-impl Direction {
-    fn get_next(&self) -> Self {
-        match *self {
-            Self::North => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::East,
-            Self::East => Self::North,
-        }
-    }
-}
+/// Directions in which elves consider moving, starting from the first one of
+/// the round.
+const MOVE_ORDER: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
 
-fn exec_round(elves_positions: &HashSet<Pos>, move_direction: &Direction) -> (HashSet<Pos>, bool) {
-    // hashmap<destination_pos, vec<origin_pos>>
-    let mut new_positions: HashMap<Pos, Vec<Pos>> = HashMap::new();
-    let mut moved = false;
-
-    // check where to move to
-    'outer: for elf_pos in elves_positions {
-        if elf_pos
-            .get_adjacent_positions()
-            .iter()
-            .any(|p| elves_positions.contains(p))
-        {
-            // we gotta move
-            let mut direction = move_direction.clone();
-            for _ in 0..4 {
-                let clearance = elf_pos.get_clearance_pos(&direction);
-                if clearance.iter().all(|p| !elves_positions.contains(p)) {
-                    // empty!
-                    new_positions
-                        .entry(clearance[0].clone())
-                        .or_insert(Vec::new())
-                        .push(elf_pos.clone());
-                    moved = true;
-                    continue 'outer;
-                }
-                direction = direction.get_next();
-            }
-        }
-
-        new_positions
-            .entry(elf_pos.clone())
-            .or_insert(Vec::new())
-            .push(elf_pos.clone());
-    }
-
-    // now move
-    let mut new_elves_positions = HashSet::new();
-    for (destination, origins) in new_positions {
-        if origins.len() == 1 {
-            new_elves_positions.insert(destination);
-        } else {
-            origins.into_iter().for_each(|p| {
-                new_elves_positions.insert(p);
-            });
-        }
+fn propose(round: usize, neighbours: &Neighbours<'_, Tile>) -> Tile {
+    if neighbours.iter().next().is_none() {
+        return Tile::Elf(None);
     }
-
-    (new_elves_positions, moved)
+    let direction = (0..4)
+        .map(|i| MOVE_ORDER[(round + i) % 4])
+        .find(|direction| {
+            [
+                *direction,
+                direction.rotate_cw_45(),
+                direction.rotate_ccw_45(),
+            ]
+            .into_iter()
+            .all(|clearance| neighbours.get(clearance).is_none())
+        });
+    Tile::Elf(direction)
 }
 
-fn get_map_bounds(elves_positions: &HashSet<Pos>) -> (Pos, Pos) {
-    let mut nw_bound = Pos(i32::MAX, i32::MAX);
-    let mut se_bound = Pos(i32::MIN, i32::MIN);
+/// Number of elves next to an empty tile that propose to move onto it.
+fn count_proposals(neighbours: &Neighbours<'_, Tile>) -> usize {
+    neighbours
+        .iter()
+        .filter(|(direction, tile)| **tile == Tile::Elf(Some(direction.opposite())))
+        .count()
+}
 
-    for pos in elves_positions {
-        if pos.0 < nw_bound.0 {
-            nw_bound = Pos(pos.0, nw_bound.1)
+/// Execute a round, and return whether any elf moved.
+fn exec_round(elves: &mut Elves, round: usize) -> bool {
+    // every elf proposes a direction
+    elves.update(DirectionSet::all(), |_, neighbours| {
+        Some(propose(round, neighbours))
+    });
+    // empty tiles count the elves that propose to move onto them
+    elves.update_with_births(DirectionSet::orthogonal(), |tile, neighbours| match tile {
+        Some(tile) => Some(tile.clone()),
+        None => {
+            let proposals = count_proposals(neighbours);
+            (proposals > 0).then_some(Tile::Proposed(proposals))
         }
-        if pos.1 < nw_bound.1 {
-            nw_bound = Pos(nw_bound.0, pos.1)
-        }
-        if pos.0 > se_bound.0 {
-            se_bound = Pos(pos.0, se_bound.1)
+    });
+    // elves move where no other elf proposed to go
+    let mut moved = false;
+    elves.update(DirectionSet::orthogonal(), |tile, neighbours| match tile {
+        Tile::Elf(Some(direction)) if neighbours.get(*direction) == Some(&Tile::Proposed(1)) => {
+            None
         }
-        if pos.1 > se_bound.1 {
-            se_bound = Pos(se_bound.0, pos.1)
+        Tile::Elf(_) => Some(Tile::Elf(None)),
+        Tile::Proposed(1) => {
+            moved = true;
+            Some(Tile::Elf(None))
         }
-    }
-
-    (nw_bound, se_bound)
+        Tile::Proposed(_) => None,
+    });
+    moved
 }
 
-fn count_empty(elves_positions: &HashSet<Pos>) -> i32 {
-    let (nw_bound, se_bound) = get_map_bounds(elves_positions);
-
-    let size = (se_bound.0 - nw_bound.0 + 1) * (se_bound.1 - nw_bound.1 + 1);
-
-    size - elves_positions.len() as i32
+fn count_empty(elves: &Elves) -> usize {
+    elves.cells().area() - elves.cells().len()
 }
 
 #[allow(dead_code)]
-fn dbg_map(elves_positions: &HashSet<Pos>) {
-    let (nw_bound, se_bound) = get_map_bounds(elves_positions);
-
-    for y in nw_bound.1..=se_bound.1 {
-        for x in nw_bound.0..=se_bound.0 {
-            if elves_positions.contains(&Pos(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
+fn dbg_map(elves: &Elves) {
+    println!(
+        "{}",
+        elves
+            .cells()
+            .render(|elf| if elf.is_some() { '#' } else { '.' })
+    );
 }
 
 pub struct AocDay23 {
-    elves_positions: HashSet<Pos>,
+    elves_positions: SparseGrid<Tile>,
 }
 
-impl AocDay<i32, i32> for AocDay23 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+impl AocDay<usize, usize> for AocDay23 {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let elves_positions = lines
             .enumerate()
-            .fold(HashSet::new(), |mut acc, (y, line)| {
+            .flat_map(|(y, line)| {
                 line.chars()
                     .enumerate()
                     .filter(|(_, char)| *char == '#')
-                    .for_each(|(x, _)| {
-                        acc.insert(Pos(x as i32, y as i32));
-                    });
+                    .map(move |(x, _)| (Pos::new(x as isize, y as isize), Tile::Elf(None)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
 
-                acc
-            });
-
-        AocDay23 { elves_positions }
+        Ok(AocDay23 { elves_positions })
     }
-    fn part1(&self) -> i32 {
-        let mut elves_positions = self.elves_positions.clone();
-        let mut move_direction = Direction::North;
-
-        for _ in 0..10 {
-            elves_positions = exec_round(&elves_positions, &move_direction).0;
-            move_direction = move_direction.get_next();
+    fn part1(&self) -> usize {
+        let mut elves = Elves::new(self.elves_positions.clone());
+        for round in 0..10 {
+            exec_round(&mut elves, round);
         }
-
-        count_empty(&elves_positions)
+        count_empty(&elves)
     }
-    fn part2(&self) -> i32 {
-        let mut elves_positions = self.elves_positions.clone();
-        let mut moved = true;
-        let mut move_direction = Direction::North;
+    fn part2(&self) -> usize {
+        let mut elves = Elves::new(self.elves_positions.clone());
         let mut round = 0;
-
-        while moved {
-            (elves_positions, moved) = exec_round(&elves_positions, &move_direction);
-            move_direction = move_direction.get_next();
+        while exec_round(&mut elves, round) {
             round += 1;
         }
-
-        round
+        round + 1
     }
 }
 
@@ -218,14 +141,16 @@ mod day23tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay23::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay23::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 110);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay23::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay23::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 20);
+        Ok(())
     }
 }
@@ -1,6 +1,8 @@
-use pathfinding::prelude::astar;
-
-use crate::AocDay;
+use aoc_common::{
+    navigation::{Direction, Toroidal, Vec2D},
+    search::{astar, Timed},
+    AocDay, DayError,
+};
 
 const NORTH_MASK: u8 = 0x1;
 const EAST_MASK: u8 = 0x2;
@@ -11,25 +13,20 @@ const WALL_MASK: u8 = 0x10;
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Pos(i32, i32); // y, x
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct TimePos(usize, Pos);
-
-fn calculate_next_blizzards(blizzards: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let (map_height, map_width) = (
-        blizzards.len() as i32 - 2,
-        blizzards.first().unwrap().len() as i32 - 2,
-    );
-    let get_position_vec = |pos: (usize, usize), vec: (i32, i32)| -> (usize, usize) {
-        if pos.0 == 0 || pos.0 == (map_height as usize + 1) {
+type TimePos = Timed<Pos>;
+
+fn calculate_next_blizzards(blizzards: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let (map_height, map_width) = (blizzards.len() - 2, blizzards.first().unwrap().len() - 2);
+    // blizzards wrap around inside the walls
+    let valley = Toroidal::new(Vec2D::new(map_width, map_height));
+    let get_position_vec = |pos: (usize, usize), direction: Direction| -> (usize, usize) {
+        if pos.0 == 0 || pos.0 == map_height + 1 {
             return pos;
         }
-        let (y, x) = (pos.0 as i32 + vec.0, pos.1 as i32 + vec.1);
-        let (y, x) = (
-            (y - 1).rem_euclid(map_height),
-            (x - 1).rem_euclid(map_width),
-        );
+        let inner_pos =
+            valley.translate(&Vec2D::new(pos.1 - 1, pos.0 - 1), &Vec2D::from(direction));
 
-        (y as usize + 1, x as usize + 1)
+        (inner_pos.y + 1, inner_pos.x + 1)
     };
 
     blizzards
@@ -42,10 +39,10 @@ fn calculate_next_blizzards(blizzards: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
                     if cell & WALL_MASK != 0 {
                         *cell
                     } else {
-                        let north = get_position_vec((y, x), (-1, 0));
-                        let east = get_position_vec((y, x), (0, 1));
-                        let south = get_position_vec((y, x), (1, 0));
-                        let west = get_position_vec((y, x), (0, -1));
+                        let north = get_position_vec((y, x), Direction::North);
+                        let east = get_position_vec((y, x), Direction::East);
+                        let south = get_position_vec((y, x), Direction::South);
+                        let west = get_position_vec((y, x), Direction::West);
 
                         (*blizzards.get(north.0).unwrap().get(north.1).unwrap() & SOUTH_MASK)
                             + (*blizzards.get(east.0).unwrap().get(east.1).unwrap() & WEST_MASK)
@@ -59,7 +56,7 @@ fn calculate_next_blizzards(blizzards: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
 }
 
 fn get_possible_next_positions(map: &mut Vec<Vec<Vec<u8>>>, pos: &TimePos) -> Vec<(TimePos, i32)> {
-    let (time, pos) = (pos.0, &pos.1);
+    let (time, pos) = (pos.time, &pos.state);
 
     if time + 1 >= map.len() {
         map.push(calculate_next_blizzards(map.last().unwrap()));
@@ -76,7 +73,7 @@ fn get_possible_next_positions(map: &mut Vec<Vec<Vec<u8>>>, pos: &TimePos) -> Ve
                 .unwrap_or(&WALL_MASK)
                 == 0
         })
-        .map(|p| (TimePos(time + 1, Pos(p.0, p.1)), 1))
+        .map(|p| (TimePos::new(Pos(p.0, p.1), time + 1), 1))
         .collect()
 }
 
@@ -123,7 +120,7 @@ pub struct AocDay24 {
 }
 
 impl AocDay<i32, i32> for AocDay24 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let blizzards = lines
             .map(|line| {
                 line.chars()
@@ -145,7 +142,7 @@ impl AocDay<i32, i32> for AocDay24 {
                 .first()
                 .and_then(|row| row.iter().position(|cell| *cell == 0))
                 .map(|x| x as i32)
-                .expect("can't find start position"),
+                .ok_or(DayError::GenericParseErr("can't find start position"))?,
         );
 
         let target_pos = Pos(
@@ -154,23 +151,23 @@ impl AocDay<i32, i32> for AocDay24 {
                 .last()
                 .and_then(|row| row.iter().position(|cell| *cell == 0))
                 .map(|x| x as i32)
-                .expect("can't find start position"),
+                .ok_or(DayError::GenericParseErr("can't find target position"))?,
         );
 
-        AocDay24 {
+        Ok(AocDay24 {
             blizzards,
             start_pos,
             target_pos,
-        }
+        })
     }
     fn part1(&self) -> i32 {
         let mut blizzards_history = vec![self.blizzards.clone()];
 
         let (_path, steps) = astar(
-            &TimePos(0, self.start_pos.clone()),
+            [TimePos::new(self.start_pos.clone(), 0)],
             |pos| get_possible_next_positions(&mut blizzards_history, pos),
-            |pos| heuristic_fun(&pos.1, &self.target_pos),
-            |pos| pos.1 == self.target_pos,
+            |pos| heuristic_fun(&pos.state, &self.target_pos),
+            |pos| pos.state == self.target_pos,
         )
         .expect("no goal reached");
 
@@ -180,26 +177,26 @@ impl AocDay<i32, i32> for AocDay24 {
         let mut blizzards_history = vec![self.blizzards.clone()];
 
         let (path, steps_first) = astar(
-            &TimePos(0, self.start_pos.clone()),
+            [TimePos::new(self.start_pos.clone(), 0)],
             |pos| get_possible_next_positions(&mut blizzards_history, pos),
-            |pos| heuristic_fun(&pos.1, &self.target_pos),
-            |pos| pos.1 == self.target_pos,
+            |pos| heuristic_fun(&pos.state, &self.target_pos),
+            |pos| pos.state == self.target_pos,
         )
         .expect("no goal reached");
 
         let (path, steps_second) = astar(
-            path.last().unwrap(),
+            path.last().cloned(),
             |pos| get_possible_next_positions(&mut blizzards_history, pos),
-            |pos| heuristic_fun(&pos.1, &self.start_pos),
-            |pos| pos.1 == self.start_pos,
+            |pos| heuristic_fun(&pos.state, &self.start_pos),
+            |pos| pos.state == self.start_pos,
         )
         .expect("no goal reached");
 
         let (_path, steps_third) = astar(
-            path.last().unwrap(),
+            path.last().cloned(),
             |pos| get_possible_next_positions(&mut blizzards_history, pos),
-            |pos| heuristic_fun(&pos.1, &self.target_pos),
-            |pos| pos.1 == self.target_pos,
+            |pos| heuristic_fun(&pos.state, &self.target_pos),
+            |pos| pos.state == self.target_pos,
         )
         .expect("no goal reached");
 
@@ -216,14 +213,16 @@ mod day24tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay24::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay24::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 18);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay24::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay24::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 54);
+        Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 fn parse_snafu(number: &str) -> i64 {
     number
@@ -57,10 +57,10 @@ pub struct AocDay25 {
 }
 
 impl AocDay<String, String> for AocDay25 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let fuel = lines.map(|line| parse_snafu(&line)).sum();
 
-        AocDay25 { fuel }
+        Ok(AocDay25 { fuel })
     }
     fn part1(&self) -> String {
         to_snafu(self.fuel)
@@ -99,8 +99,9 @@ mod day25tests {
     }
 
     #[test]
-    fn part1() {
-        let day = AocDay25::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay25::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), "2=-1=0");
+        Ok(())
     }
 }
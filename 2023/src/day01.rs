@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 const DIGIT_PATTERNS: &[&str] = &[
     "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1", "2",
@@ -10,10 +10,10 @@ pub struct AocDay01 {
 }
 
 impl AocDay<u32, usize> for AocDay01 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        AocDay01 {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        Ok(AocDay01 {
             lines: lines.collect(),
-        }
+        })
     }
     fn part1(&self) -> u32 {
         self.lines
@@ -83,14 +83,16 @@ mod day01tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay01::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay01::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 142);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay01::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay01::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part2(), 281);
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 struct Game {
     id: u32,
@@ -8,15 +8,16 @@ struct Game {
 }
 
 impl FromStr for Game {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let (id, subgames) = line.split_once(": ").ok_or(ParseErr)?;
+        let (id, subgames) = line
+            .split_once(": ")
+            .ok_or(DayError::GenericParseErr("game must contain :"))?;
         let id: u32 = id
             .split(' ')
-            .last()
-            .ok_or(ParseErr)?
-            .parse()
-            .map_err(|_| ParseErr)?;
+            .next_back()
+            .ok_or(DayError::GenericParseErr("game must have an id"))?
+            .parse()?;
         let subgames = subgames
             .split("; ")
             .map(|subgame_str| subgame_str.parse())
@@ -33,17 +34,19 @@ struct SubGame {
 }
 
 impl FromStr for SubGame {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(subgame_str: &str) -> Result<Self, Self::Err> {
         let mut subgame = SubGame::new();
         subgame_str.split(", ").try_for_each(|color_str| {
-            let (value, color_name) = color_str.split_once(' ').ok_or(ParseErr)?;
-            let value: u32 = value.parse().map_err(|_| ParseErr)?;
+            let (value, color_name) = color_str
+                .split_once(' ')
+                .ok_or(DayError::GenericParseErr("cube count must contain a space"))?;
+            let value: u32 = value.parse()?;
             match color_name {
                 "red" => subgame.red = value,
                 "green" => subgame.green = value,
                 "blue" => subgame.blue = value,
-                _ => unreachable!("unknown color"),
+                _ => return Err(DayError::GenericParseErr("unknown color")),
             }
             Ok(())
         })?;
@@ -65,20 +68,15 @@ impl SubGame {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay02 {
     games: Vec<Game>,
 }
 
 impl AocDay<u32, u32> for AocDay02 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let games = lines
-            .map(|line| line.parse().expect("failed to parse game"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let games = parse_lines(lines)?;
 
-        AocDay02 { games }
+        Ok(AocDay02 { games })
     }
     fn part1(&self) -> u32 {
         self.games
@@ -121,14 +119,16 @@ mod day02tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay02::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay02::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 8);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay02::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay02::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 2286);
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 struct Pos(i32, i32);
@@ -48,16 +48,13 @@ impl Number {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay03 {
     symbols: HashMap<Pos, char>,
     numbers: Vec<Number>,
 }
 
 impl AocDay<u32, u32> for AocDay03 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut symbols = HashMap::new();
         let mut numbers = vec![];
 
@@ -68,7 +65,7 @@ impl AocDay<u32, u32> for AocDay03 {
             for (x, char) in line.chars().enumerate() {
                 if char.is_ascii_digit() {
                     curr_number *= 10;
-                    curr_number += char.to_digit(10).unwrap();
+                    curr_number += char.to_digit(10).expect("char is an ASCII digit");
                     curr_number_len += 1;
                     if curr_number_pos.is_none() {
                         curr_number_pos = Some(Pos::new(x, y));
@@ -90,7 +87,7 @@ impl AocDay<u32, u32> for AocDay03 {
                         symbols.insert(Pos::new(x, y), char);
                     }
                     '.' => {}
-                    _ => unreachable!("unknown char in map: {}", char),
+                    _ => return Err(DayError::GenericParseErr("unknown char in map")),
                 }
             }
             if let Some(pos) = curr_number_pos {
@@ -102,7 +99,7 @@ impl AocDay<u32, u32> for AocDay03 {
             }
         }
 
-        AocDay03 { symbols, numbers }
+        Ok(AocDay03 { symbols, numbers })
     }
     fn part1(&self) -> u32 {
         self.numbers
@@ -111,7 +108,7 @@ impl AocDay<u32, u32> for AocDay03 {
                 number
                     .get_adjacent_pos()
                     .iter()
-                    .any(|pos| self.symbols.get(pos).is_some())
+                    .any(|pos| self.symbols.contains_key(pos))
             })
             .map(|number| number.value)
             .sum()
@@ -153,14 +150,16 @@ mod day03tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay03::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay03::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 4361);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay03::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay03::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 467835);
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 #[derive(Clone)]
 struct Card {
@@ -18,41 +18,40 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, numbers) = s.split_once(": ").ok_or(ParseErr)?;
+        let (_, numbers) = s
+            .split_once(": ")
+            .ok_or(DayError::GenericParseErr("card must contain :"))?;
 
-        let (winning, own) = numbers.split_once(" | ").ok_or(ParseErr)?;
+        let (winning, own) = numbers
+            .split_once(" | ")
+            .ok_or(DayError::GenericParseErr("card must contain |"))?;
 
         Ok(Card {
             winning: winning
                 .split(' ')
                 .filter(|s| !s.is_empty())
-                .map(|n| n.parse().map_err(|_| ParseErr))
+                .map(|n| n.parse())
                 .collect::<Result<_, _>>()?,
             own: own
                 .split(' ')
                 .filter(|s| !s.is_empty())
-                .map(|n| n.parse().map_err(|_| ParseErr))
+                .map(|n| n.parse())
                 .collect::<Result<_, _>>()?,
         })
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay04 {
     cards: Vec<Card>,
 }
 
 impl AocDay<u32, u32> for AocDay04 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let cards = lines
-            .map(|line| line.parse().expect("failed to parse card"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let cards = parse_lines(lines)?;
 
-        AocDay04 { cards }
+        Ok(AocDay04 { cards })
     }
     fn part1(&self) -> u32 {
         self.cards
@@ -109,14 +108,16 @@ mod day04tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay04::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay04::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 13);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay04::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay04::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 30);
+        Ok(())
     }
 }
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
-use crate::AocDay;
+use aoc_common::{interval_set::IntervalSet, AocDay, DayError};
 
 #[derive(Debug)]
 struct ConvertEntry {
@@ -15,27 +15,22 @@ impl ConvertEntry {
     fn in_range(&self, from: u32) -> bool {
         self.src_start <= from && self.src_end >= from
     }
-    fn get_range(&self, from: &Range) -> Option<Range> {
-        // https://scicomp.stackexchange.com/questions/26258/the-easiest-way-to-find-intersection-of-two-intervals
-        let start = from.start.max(self.src_start);
-        let end = from.end.min(self.src_end);
-
-        if start > end {
-            None
-        } else {
-            Some(Range { start, end })
-        }
-    }
 }
 
 impl FromStr for ConvertEntry {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut it = s.split_whitespace();
 
-        let dest_start = it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?;
-        let src_start = it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?;
-        let length: u32 = it.next().ok_or(ParseErr)?.parse().map_err(|_| ParseErr)?;
+        let mut next = || {
+            it.next().ok_or(DayError::GenericParseErr(
+                "convert entry must have 3 numbers",
+            ))
+        };
+
+        let dest_start = next()?.parse()?;
+        let src_start = next()?.parse()?;
+        let length: u32 = next()?.parse()?;
 
         Ok(ConvertEntry {
             dest_start,
@@ -63,69 +58,32 @@ impl ConvertMap {
         }
         from
     }
-    fn convert_range(&self, from: Range) -> Vec<Range> {
-        let mut ranges = vec![from];
-        let mut resulting_ranges = vec![];
-        for entry in &self.entries {
-            ranges = ranges
-                .into_iter()
-                .flat_map(|range| match entry.get_range(&range) {
-                    Some(matched_range) => {
-                        let delta = entry.dest_start.abs_diff(entry.src_start);
-                        let (start, end) = if entry.dest_start > entry.src_start {
-                            (matched_range.start + delta, matched_range.end + delta)
-                        } else {
-                            (matched_range.start - delta, matched_range.end - delta)
-                        };
-
-                        resulting_ranges.push(Range { start, end });
-
-                        let mut new_ranges = vec![];
-                        if range.start != matched_range.start {
-                            new_ranges.push(Range {
-                                start: range.start,
-                                end: matched_range.start - 1,
-                            });
-                        }
-                        if range.end != matched_range.end {
-                            new_ranges.push(Range {
-                                start: matched_range.start + 1,
-                                end: range.end,
-                            });
-                        }
-                        new_ranges.into_iter()
-                    }
-                    None => vec![range].into_iter(),
-                })
-                .collect();
-        }
-        ranges.into_iter().for_each(|r| resulting_ranges.push(r));
-        resulting_ranges
+    fn convert_set(&self, from: &IntervalSet<u32>) -> IntervalSet<u32> {
+        from.map_piecewise(
+            self.entries
+                .iter()
+                .map(|entry| (entry.src_start..=entry.src_end, entry.dest_start)),
+        )
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Range {
-    start: u32,
-    end: u32,
-}
-
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay05 {
     seeds: Vec<u32>,
     convert_maps: Vec<ConvertMap>,
 }
 
 impl AocDay<u32, u32> for AocDay05 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
-        let seeds = lines.next().expect("failed to parse seeds");
-        let (_, seeds) = seeds.split_once(": ").expect("failed to parse seeds");
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let seeds = lines
+            .next()
+            .ok_or(DayError::GenericParseErr("seeds not in input"))?;
+        let (_, seeds) = seeds
+            .split_once(": ")
+            .ok_or(DayError::GenericParseErr("failed to parse seeds"))?;
         let seeds = seeds
             .split_whitespace()
-            .map(|n| n.parse().expect("failed to parse seed number"))
-            .collect();
+            .map(|n| n.parse())
+            .collect::<Result<_, _>>()?;
 
         let mut convert_maps = vec![];
         let mut entries = vec![];
@@ -141,17 +99,17 @@ impl AocDay<u32, u32> for AocDay05 {
             if line.ends_with(" map:") {
                 continue;
             }
-            entries.push(line.parse().expect("failed to parse convertion entry"));
+            entries.push(line.parse()?);
         }
 
         if !entries.is_empty() {
             convert_maps.push(ConvertMap { entries });
         }
 
-        AocDay05 {
+        Ok(AocDay05 {
             seeds,
             convert_maps,
-        }
+        })
     }
     fn part1(&self) -> u32 {
         self.seeds
@@ -165,28 +123,18 @@ impl AocDay<u32, u32> for AocDay05 {
             .expect("no seeds in input")
     }
     fn part2(&self) -> u32 {
-        let ranges: Vec<Range> = self
+        let seeds: IntervalSet<u32> = self
             .seeds
             .iter()
             .tuples()
-            .map(|(&start, &len)| Range {
-                start,
-                end: len - 1 + start,
-            })
+            .map(|(&start, &len)| start..=len - 1 + start)
             .collect();
 
         self.convert_maps
             .iter()
-            // rust does not like accumulating with an iterator :(
-            .fold(ranges, |acc, converter| {
-                acc.into_iter()
-                    .flat_map(|r| converter.convert_range(r))
-                    .collect()
-            })
-            .iter()
+            .fold(seeds, |acc, converter| converter.convert_set(&acc))
             .min()
             .expect("no seeds in input")
-            .start
     }
 }
 
@@ -197,14 +145,16 @@ mod day05tests {
     const INPUT: &str = include_str!("../inputs/day05_example.txt");
 
     #[test]
-    fn part1() {
-        let day = AocDay05::preprocessing(INPUT.lines().map(String::from));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay05::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(day.part1(), 35);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay05::preprocessing(INPUT.lines().map(String::from));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay05::preprocessing(INPUT.lines().map(String::from))?;
         assert_eq!(day.part2(), 46);
+        Ok(())
     }
 }
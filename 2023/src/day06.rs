@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 fn ways_to_win(time: u64, distance: u64) -> u64 {
     let held_t = time / 2;
@@ -19,7 +19,7 @@ fn ways_to_win(time: u64, distance: u64) -> u64 {
     }
 
     let count_half = held_t - left + 1;
-    if time % 2 == 0 {
+    if time.is_multiple_of(2) {
         count_half * 2 - 1
     } else {
         count_half * 2
@@ -33,23 +33,23 @@ pub struct AocDay06 {
 }
 
 impl AocDay<u64, u64> for AocDay06 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let time = lines
             .next()
-            .expect("times not in input")
+            .ok_or(DayError::GenericParseErr("times not in input"))?
             .split_whitespace()
             .skip(1)
-            .map(|i| i.parse().expect("time is not a number"))
-            .collect();
+            .map(|i| i.parse())
+            .collect::<Result<_, _>>()?;
         let distance = lines
             .next()
-            .expect("distances not in input")
+            .ok_or(DayError::GenericParseErr("distances not in input"))?
             .split_whitespace()
             .skip(1)
-            .map(|i| i.parse().expect("distance is not a number"))
-            .collect();
+            .map(|i| i.parse())
+            .collect::<Result<_, _>>()?;
 
-        AocDay06 { time, distance }
+        Ok(AocDay06 { time, distance })
     }
     fn part1(&self) -> u64 {
         self.time
@@ -86,14 +86,16 @@ mod day06tests {
     const INPUT: &[&str] = &["Time:      7  15   30", "Distance:  9  40  200"];
 
     #[test]
-    fn part1() {
-        let day = AocDay06::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 288);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay06::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay06::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 71503);
+        Ok(())
     }
 }
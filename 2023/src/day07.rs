@@ -1,21 +1,35 @@
 use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, TryFromChar)]
 enum Label {
+    #[char_repr = 'A']
     A,
+    #[char_repr = 'K']
     K,
+    #[char_repr = 'Q']
     Q,
+    #[char_repr = 'J']
     J,
+    #[char_repr = 'T']
     T,
+    #[char_repr = '9']
     N9,
+    #[char_repr = '8']
     N8,
+    #[char_repr = '7']
     N7,
+    #[char_repr = '6']
     N6,
+    #[char_repr = '5']
     N5,
+    #[char_repr = '4']
     N4,
+    #[char_repr = '3']
     N3,
+    #[char_repr = '2']
     N2,
 }
 
@@ -30,28 +44,6 @@ impl Label {
     }
 }
 
-impl TryFrom<char> for Label {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'A' => Ok(Self::A),
-            'K' => Ok(Self::K),
-            'Q' => Ok(Self::Q),
-            'J' => Ok(Self::J),
-            'T' => Ok(Self::T),
-            '9' => Ok(Self::N9),
-            '8' => Ok(Self::N8),
-            '7' => Ok(Self::N7),
-            '6' => Ok(Self::N6),
-            '5' => Ok(Self::N5),
-            '4' => Ok(Self::N4),
-            '3' => Ok(Self::N3),
-            '2' => Ok(Self::N2),
-            _ => Err(ParseErr),
-        }
-    }
-}
-
 fn by_type<const PART2: bool>(cards: &[Label]) -> HashMap<Label, usize> {
     let mut map = HashMap::new();
 
@@ -142,23 +134,22 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (cards, bid) = s.split_once(' ').ok_or(ParseErr)?;
+        let (cards, bid) = s
+            .split_once(' ')
+            .ok_or(DayError::GenericParseErr("hand must contain a space"))?;
 
         Ok(Self {
             cards: cards
                 .chars()
                 .map(|c| c.try_into())
                 .collect::<Result<_, _>>()?,
-            bid: bid.parse().map_err(|_| ParseErr)?,
+            bid: bid.parse()?,
         })
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 fn get_hands_winnings(sorted_hands: &[Hand]) -> u64 {
     sorted_hands
         .iter()
@@ -173,12 +164,10 @@ pub struct AocDay07 {
 }
 
 impl AocDay<u64, u64> for AocDay07 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let hands = lines
-            .map(|line| line.parse().expect("unable to parse hand"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let hands = parse_lines(lines)?;
 
-        AocDay07 { hands }
+        Ok(AocDay07 { hands })
     }
     fn part1(&self) -> u64 {
         let mut sorted_hands = self.hands.clone();
@@ -207,19 +196,21 @@ mod day07tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay07::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay07::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 6440);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay07::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay07::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 5905);
+        Ok(())
     }
 
     #[test]
-    fn part2_hands() -> Result<(), ParseErr> {
+    fn part2_hands() -> Result<(), DayError> {
         let hand: Hand = "JJJJJ 0".parse()?;
         assert_eq!(hand.get_hand_type::<true>(), HandType::FiveKind);
 
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 enum Direction {
     Left,
@@ -44,27 +44,32 @@ pub struct AocDay08 {
 }
 
 impl AocDay<u64, u64> for AocDay08 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let directions = lines
             .next()
-            .expect("cannot find line with directions")
+            .ok_or(DayError::GenericParseErr(
+                "cannot find line with directions",
+            ))?
             .chars()
             .map(|c| match c {
-                'L' => Direction::Left,
-                'R' => Direction::Right,
-                _ => unreachable!("char direction"),
+                'L' => Ok(Direction::Left),
+                'R' => Ok(Direction::Right),
+                _ => Err(DayError::GenericParseErr("unknown direction")),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        lines.next().expect("cannot find separation line");
+        lines
+            .next()
+            .ok_or(DayError::GenericParseErr("cannot find separation line"))?;
 
         let mut nodes = HashMap::new();
 
         for line in lines {
-            let (name, target) = line.split_once(" = ").expect("invalid node");
-            let target = target.strip_prefix('(').expect("invalid node");
-            let target = target.strip_suffix(')').expect("invalid node");
-            let (left, right) = target.split_once(", ").expect("invalid node");
+            let invalid_node = || DayError::GenericParseErr("invalid node");
+            let (name, target) = line.split_once(" = ").ok_or_else(invalid_node)?;
+            let target = target.strip_prefix('(').ok_or_else(invalid_node)?;
+            let target = target.strip_suffix(')').ok_or_else(invalid_node)?;
+            let (left, right) = target.split_once(", ").ok_or_else(invalid_node)?;
 
             nodes.insert(
                 name.to_string(),
@@ -75,7 +80,7 @@ impl AocDay<u64, u64> for AocDay08 {
             );
         }
 
-        AocDay08 { directions, nodes }
+        Ok(AocDay08 { directions, nodes })
     }
     fn part1(&self) -> u64 {
         steps_to_z::<false>(self, "AAA")
@@ -115,14 +120,16 @@ mod day08tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay08::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay08::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 6);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay08::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay08::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part2(), 6);
+        Ok(())
     }
 }
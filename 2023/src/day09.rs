@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 fn find_all_non_zero_differences(sequence: &[i32]) -> Vec<Vec<i32>> {
     let mut diffs = vec![sequence.to_vec()];
@@ -39,16 +39,16 @@ pub struct AocDay09 {
 }
 
 impl AocDay<i32, i32> for AocDay09 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let numbers = lines
             .map(|line| {
                 line.split_whitespace()
-                    .map(|n| n.parse().expect("found non-integer in input"))
-                    .collect()
+                    .map(|n| n.parse())
+                    .collect::<Result<_, _>>()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        AocDay09 { numbers }
+        Ok(AocDay09 { numbers })
     }
     fn part1(&self) -> i32 {
         self.numbers.iter().map(|seq| find_next_numbers(seq)).sum()
@@ -65,14 +65,16 @@ mod day09tests {
     const INPUT: &[&str] = &["0 3 6 9 12 15", "1 3 6 10 15 21", "10 13 16 21 30 45"];
 
     #[test]
-    fn part1() {
-        let day = AocDay09::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay09::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 114);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay09::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay09::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 2);
+        Ok(())
     }
 }
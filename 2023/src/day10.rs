@@ -1,16 +1,25 @@
 use std::collections::HashSet;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, TryFromChar)]
 enum Tile {
+    #[char_repr = '|']
     Vertical,
+    #[char_repr = '-']
     Horizontal,
+    #[char_repr = 'L']
     NorthEast,
+    #[char_repr = 'J']
     NorthWest,
+    #[char_repr = '7']
     SouthWest,
+    #[char_repr = 'F']
     SouthEast,
+    #[char_repr = '.']
     Ground,
+    #[char_repr = 'S']
     Start,
 }
 
@@ -35,7 +44,7 @@ impl Tile {
 
     /// Convert pipe locations to a tile type.
     /// Used to convert the start tile into an actual tile
-    fn from_directions(dirs: &[Direction]) -> Self {
+    fn from_directions(dirs: &[Direction]) -> Result<Self, DayError> {
         [
             Self::Vertical,
             Self::Horizontal,
@@ -51,24 +60,9 @@ impl Tile {
 
             tile_set.eq(&other_set)
         })
-        .expect("cannot find tile from list of directions")
-    }
-}
-
-impl TryFrom<char> for Tile {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '|' => Ok(Tile::Vertical),
-            '-' => Ok(Tile::Horizontal),
-            'L' => Ok(Tile::NorthEast),
-            'J' => Ok(Tile::NorthWest),
-            '7' => Ok(Tile::SouthWest),
-            'F' => Ok(Tile::SouthEast),
-            '.' => Ok(Tile::Ground),
-            'S' => Ok(Tile::Start),
-            _ => Err(ParseErr),
-        }
+        .ok_or(DayError::GenericParseErr(
+            "start tile must be connected to exactly two pipes",
+        ))
     }
 }
 
@@ -149,17 +143,14 @@ fn get_start_directions(tiles: &[Vec<Tile>], start_pos: (usize, usize)) -> Vec<D
         .collect()
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay10 {
     tiles: Vec<Vec<Tile>>,
     pipe_positions: HashSet<(usize, usize)>,
-    start_directions: Vec<Direction>,
+    start_tile: Tile,
 }
 
 impl AocDay<usize, u32> for AocDay10 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut start_pos = None;
         let tiles: Vec<_> = lines
             .enumerate()
@@ -167,45 +158,52 @@ impl AocDay<usize, u32> for AocDay10 {
                 line.chars()
                     .enumerate()
                     .map(|(x, c)| {
-                        let tile = c.try_into().expect("invalid character in map");
+                        let tile = c.try_into()?;
                         if tile == Tile::Start {
                             start_pos = Some((x, y));
                         }
-                        tile
+                        Ok(tile)
                     })
-                    .collect()
+                    .collect::<Result<_, DayError>>()
             })
-            .collect();
-        let start_pos = start_pos.expect("input does not contain a start position");
+            .collect::<Result<_, _>>()?;
+        let start_pos = start_pos.ok_or(DayError::GenericParseErr(
+            "input does not contain a start position",
+        ))?;
 
         // this is the same for part 1 and part 2, so do it here
         let start_directions = get_start_directions(&tiles, start_pos);
-        let move_to = start_directions
-            .first()
-            .expect("start tile is not connected to other pipes");
+        let start_tile = Tile::from_directions(&start_directions)?;
+        let move_to = &start_directions[0];
 
         let mut pipe_positions = HashSet::new();
         pipe_positions.insert(start_pos);
-        let mut curr_pos = move_to.move_pos(start_pos).expect("pipe outside map range");
+        let mut curr_pos = move_to
+            .move_pos(start_pos)
+            .ok_or(DayError::GenericParseErr("pipe loop goes outside the map"))?;
         let mut from_dir = move_to.get_opposite();
         while curr_pos != start_pos {
-            let curr_tile = get_tile(&tiles, curr_pos);
-            let next_dir = curr_tile
-                .get_directions()
+            let directions = get_tile(&tiles, curr_pos).get_directions();
+            if !directions.contains(&from_dir) {
+                return Err(DayError::GenericParseErr("pipe loop is not closed"));
+            }
+            let next_dir = directions
                 .into_iter()
                 .find(|d| *d != from_dir)
-                .expect("cannot find next direction to move to");
+                .ok_or(DayError::GenericParseErr("pipe loop is not closed"))?;
 
             pipe_positions.insert(curr_pos);
-            curr_pos = next_dir.move_pos(curr_pos).expect("pipe outside map range");
+            curr_pos = next_dir
+                .move_pos(curr_pos)
+                .ok_or(DayError::GenericParseErr("pipe loop goes outside the map"))?;
             from_dir = next_dir.get_opposite();
         }
 
-        AocDay10 {
+        Ok(AocDay10 {
             tiles,
             pipe_positions,
-            start_directions,
-        }
+            start_tile,
+        })
     }
     fn part1(&self) -> usize {
         self.pipe_positions.len() / 2
@@ -225,9 +223,9 @@ impl AocDay<usize, u32> for AocDay10 {
                 }
 
                 let tile = if *tile == Tile::Start {
-                    Tile::from_directions(&self.start_directions)
+                    &self.start_tile
                 } else {
-                    tile.clone()
+                    tile
                 };
 
                 match tile {
@@ -259,7 +257,6 @@ impl AocDay<usize, u32> for AocDay10 {
 }
 
 #[cfg(test)]
-
 mod day10tests {
     use super::*;
 
@@ -302,23 +299,25 @@ mod day10tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay10::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay10::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 4);
 
-        let day = AocDay10::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+        let day = AocDay10::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part1(), 8);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay10::preprocessing(INPUT3.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay10::preprocessing_tests(INPUT3)?;
         assert_eq!(day.part2(), 4);
 
-        let day = AocDay10::preprocessing(INPUT4.iter().map(|x| String::from(*x)));
+        let day = AocDay10::preprocessing_tests(INPUT4)?;
         assert_eq!(day.part2(), 8);
 
-        let day = AocDay10::preprocessing(INPUT5.iter().map(|x| String::from(*x)));
+        let day = AocDay10::preprocessing_tests(INPUT5)?;
         assert_eq!(day.part2(), 10);
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 struct Pos {
     x: usize,
@@ -51,32 +51,34 @@ impl AocDay11 {
 }
 
 impl AocDay<usize, usize> for AocDay11 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut galaxies = Vec::new();
         let mut galaxies_x = Vec::new();
         let mut galaxies_y = Vec::new();
-        lines.enumerate().for_each(|(y, line)| {
-            line.chars().enumerate().for_each(|(x, c)| match c {
-                '#' => {
-                    galaxies.push(Pos { x, y });
-                    galaxies_x.push(x);
-                    galaxies_y.push(y);
+        for (y, line) in lines.enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => {
+                        galaxies.push(Pos { x, y });
+                        galaxies_x.push(x);
+                        galaxies_y.push(y);
+                    }
+                    '.' => {}
+                    _ => return Err(DayError::GenericParseErr("unknown tile")),
                 }
-                '.' => {}
-                _ => unreachable!(),
-            })
-        });
+            }
+        }
 
         galaxies_x.sort();
         galaxies_x.dedup();
         // galaxies_y is already sorted
         galaxies_y.dedup();
 
-        AocDay11 {
+        Ok(AocDay11 {
             galaxies,
             galaxies_x,
             galaxies_y,
-        }
+        })
     }
     fn part1(&self) -> usize {
         self.distance_between_galaxies::<2>()
@@ -104,15 +106,17 @@ mod day11tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay11::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay11::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 374);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay11::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay11::preprocessing_tests(INPUT)?;
         assert_eq!(day.distance_between_galaxies::<10>(), 1030);
         assert_eq!(day.distance_between_galaxies::<100>(), 8410);
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use ndarray::Array3;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Spring {
@@ -123,27 +123,32 @@ pub struct AocDay12 {
 }
 
 impl AocDay<u64, u64> for AocDay12 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let rows = lines
             .map(|line| {
-                let (springs, cont) = line.split_once(' ').unwrap();
+                let (springs, cont) = line
+                    .split_once(' ')
+                    .ok_or(DayError::GenericParseErr("row must contain a space"))?;
 
-                Row {
+                Ok(Row {
                     springs: springs
                         .chars()
                         .map(|c| match c {
-                            '.' => Spring::Operational,
-                            '#' => Spring::Damaged,
-                            '?' => Spring::Unknown,
-                            _ => unreachable!("unknown spring"),
+                            '.' => Ok(Spring::Operational),
+                            '#' => Ok(Spring::Damaged),
+                            '?' => Ok(Spring::Unknown),
+                            _ => Err(DayError::GenericParseErr("unknown spring")),
                         })
-                        .collect(),
-                    contiguous: cont.split(',').map(|i| i.parse().unwrap()).collect(),
-                }
+                        .collect::<Result<_, _>>()?,
+                    contiguous: cont
+                        .split(',')
+                        .map(|i| i.parse())
+                        .collect::<Result<_, _>>()?,
+                })
             })
-            .collect();
+            .collect::<Result<_, DayError>>()?;
 
-        AocDay12 { rows }
+        Ok(AocDay12 { rows })
     }
     fn part1(&self) -> u64 {
         self.rows.iter().map(|row| row.count_possibilities()).sum()
@@ -182,14 +187,16 @@ mod day12tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay12::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay12::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 21);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay12::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay12::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 525152);
+        Ok(())
     }
 }
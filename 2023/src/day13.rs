@@ -1,33 +1,22 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, TryFromChar)]
 enum Material {
+    #[char_repr = '#']
     Rock,
+    #[char_repr = '.']
     Ash,
 }
 
-impl TryFrom<char> for Material {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '.' => Ok(Material::Ash),
-            '#' => Ok(Material::Rock),
-            _ => Err(ParseErr),
-        }
-    }
-}
-
-#[derive(Debug)]
-struct ParseErr;
-
-fn is_reflection_x<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>, y: usize) -> bool {
+fn is_reflection_x<const NUM_DIFF: u8>(map: &[Vec<Material>], y: usize) -> bool {
     let mut difference_count = 0;
     let mut i = 0;
     loop {
         let upper = y + i;
         let lower = y - i - 1;
-        for j in 0..map[0].len() {
-            if map[upper][j] != map[lower][j] {
+        for (a, b) in map[upper].iter().zip(&map[lower]) {
+            if a != b {
                 if difference_count >= NUM_DIFF {
                     return false;
                 }
@@ -44,11 +33,11 @@ fn is_reflection_x<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>, y: usize) -> bo
     difference_count == NUM_DIFF
 }
 
-fn get_reflection_x<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>) -> Option<usize> {
+fn get_reflection_x<const NUM_DIFF: u8>(map: &[Vec<Material>]) -> Option<usize> {
     (1..map.len()).find(|&y| is_reflection_x::<NUM_DIFF>(map, y))
 }
 
-fn is_reflection_y<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>, x: usize) -> bool {
+fn is_reflection_y<const NUM_DIFF: u8>(map: &[Vec<Material>], x: usize) -> bool {
     let mut differences_count = 0;
     let mut i = 0;
     loop {
@@ -72,7 +61,7 @@ fn is_reflection_y<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>, x: usize) -> bo
     differences_count == NUM_DIFF
 }
 
-fn get_reflection_y<const NUM_DIFF: u8>(map: &Vec<Vec<Material>>) -> Option<usize> {
+fn get_reflection_y<const NUM_DIFF: u8>(map: &[Vec<Material>]) -> Option<usize> {
     (1..map[0].len()).find(|&x| is_reflection_y::<NUM_DIFF>(map, x))
 }
 
@@ -98,7 +87,7 @@ impl AocDay13 {
 }
 
 impl AocDay<usize, usize> for AocDay13 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut patterns = vec![];
         let mut pattern = vec![];
 
@@ -112,8 +101,8 @@ impl AocDay<usize, usize> for AocDay13 {
             }
             pattern.push(
                 line.chars()
-                    .map(|c| c.try_into().expect("invalid character in map"))
-                    .collect(),
+                    .map(|c| c.try_into())
+                    .collect::<Result<_, _>>()?,
             );
         }
 
@@ -121,7 +110,7 @@ impl AocDay<usize, usize> for AocDay13 {
             patterns.push(pattern);
         }
 
-        AocDay13 { patterns }
+        Ok(AocDay13 { patterns })
     }
     fn part1(&self) -> usize {
         self.solve::<0>()
@@ -154,14 +143,16 @@ mod day13tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay13::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay13::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 405);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay13::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay13::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 400);
+        Ok(())
     }
 }
@@ -1,29 +1,18 @@
 use ndarray::prelude::*;
 
-use crate::AocDay;
+use aoc_common::{cycle, AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, TryFromChar)]
 enum Rock {
+    #[char_repr = 'O']
     Round,
+    #[char_repr = '#']
     Square,
+    #[char_repr = '.']
     Empty,
 }
 
-impl TryFrom<char> for Rock {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'O' => Ok(Rock::Round),
-            '#' => Ok(Rock::Square),
-            '.' => Ok(Rock::Empty),
-            _ => Err(ParseErr),
-        }
-    }
-}
-
-#[derive(Debug)]
-struct ParseErr;
-
 fn roll_generic<const OUTER_AXIS: usize, const REV: bool>(map: &Array2<Rock>) -> Array2<Rock> {
     let mut last_outer_i = vec![0; map.shape()[1 - OUTER_AXIS]];
     let mut new_map = map.clone();
@@ -103,16 +92,18 @@ fn dbg_map(map: &Array2<Rock>) {
     println!();
 }
 
-fn find_cycle(results: &[usize]) -> Vec<usize> {
-    for i in (results.len() / 2)..results.len() {
-        let cycle_size = results.len() - i;
-        if results[i..] == results[(i - cycle_size)..i] {
-            // cycle!
-            return results[i..].to_vec();
-        }
-    }
+fn spin_cycle(map: &mut Array2<Rock>) {
+    *map = roll_east(&roll_south(&roll_west(&roll_north(map))));
+}
 
-    panic!("no cycle found");
+fn north_load(map: &Array2<Rock>) -> usize {
+    let height = map.nrows();
+    map.indexed_iter()
+        .map(|((y, _), rock)| match rock {
+            Rock::Round => height - y,
+            _ => 0,
+        })
+        .sum()
 }
 
 pub struct AocDay14 {
@@ -120,54 +111,32 @@ pub struct AocDay14 {
 }
 
 impl AocDay<usize, usize> for AocDay14 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut lines = lines.peekable();
-        let width = lines.peek().expect("map does not have any rows").len();
+        let width = lines
+            .peek()
+            .ok_or(DayError::GenericParseErr("map does not have any rows"))?
+            .len();
         let map: Vec<Rock> = lines
-            .flat_map(|row| {
-                row.chars()
-                    .map(|c| c.try_into().expect("failed to parse character in map"))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-
-        AocDay14 {
+            .flat_map(|row| row.chars().map(Rock::try_from).collect::<Vec<_>>())
+            .collect::<Result<_, _>>()?;
+
+        Ok(AocDay14 {
             map: Array2::from_shape_vec((map.len() / width, width), map)
-                .expect("failed to build array2"),
-        }
+                .map_err(|_| DayError::GenericParseErr("map rows must have the same length"))?,
+        })
     }
     fn part1(&self) -> usize {
-        let height = self.map.shape()[0];
-        roll_generic::<0, false>(&self.map)
-            .indexed_iter()
-            .map(|((y, _), rock)| match rock {
-                Rock::Round => height - y,
-                _ => 0,
-            })
-            .sum()
+        north_load(&roll_north(&self.map))
     }
     fn part2(&self) -> usize {
-        let height = self.map.nrows();
-        let (_, results) = (0..1000).fold((self.map.clone(), vec![]), |(map, mut results), _| {
-            let map = roll_north(&map);
-            let map = roll_west(&map);
-            let map = roll_south(&map);
-            let map = roll_east(&map);
-            let result: usize = map
-                .indexed_iter()
-                .map(|((y, _), rock)| match rock {
-                    Rock::Round => height - y,
-                    _ => 0,
-                })
-                .sum();
-            results.push(result);
-            (map, results)
-        });
-
-        let cycle = find_cycle(&results);
-
-        let remaining = 1000000000 - 1000;
-        cycle[(cycle.len() - 1 + remaining) % cycle.len()]
+        cycle::fast_forward(
+            self.map.clone(),
+            1_000_000_000,
+            spin_cycle,
+            |map| map.clone(),
+            north_load,
+        )
     }
 }
 
@@ -189,14 +158,16 @@ mod day14tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay14::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay14::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 136);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay14::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay14::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 64);
+        Ok(())
     }
 }
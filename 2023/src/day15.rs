@@ -1,4 +1,4 @@
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 fn hash(s: &str) -> usize {
     s.bytes()
@@ -10,11 +10,13 @@ pub struct AocDay15 {
 }
 
 impl AocDay<usize, usize> for AocDay15 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
-        let line = lines.next().expect("input must contain at least one line");
+    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let line = lines.next().ok_or(DayError::GenericParseErr(
+            "input must contain at least one line",
+        ))?;
         let steps = line.trim().split(',').map(String::from).collect();
 
-        AocDay15 { steps }
+        Ok(AocDay15 { steps })
     }
     fn part1(&self) -> usize {
         self.steps.iter().map(|s| hash(s)).sum()
@@ -67,14 +69,16 @@ mod day15tests {
     const INPUT: &[&str] = &["rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"];
 
     #[test]
-    fn part1() {
-        let day = AocDay15::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay15::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 1320);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay15::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay15::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 145);
+        Ok(())
     }
 }
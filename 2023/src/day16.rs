@@ -1,13 +1,19 @@
 use ndarray::{Array2, Array3, Axis};
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, TryFromChar)]
 enum Tile {
+    #[char_repr = '.']
     Empty,
+    #[char_repr = '\\']
     MirrorBackward,
+    #[char_repr = '/']
     MirrorForward,
+    #[char_repr = '|']
     SplitterVertical,
+    #[char_repr = '-']
     SplitterHorizontal,
 }
 
@@ -55,20 +61,6 @@ impl Tile {
     }
 }
 
-impl TryFrom<char> for Tile {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '.' => Ok(Self::Empty),
-            '/' => Ok(Self::MirrorForward),
-            '\\' => Ok(Self::MirrorBackward),
-            '|' => Ok(Self::SplitterVertical),
-            '-' => Ok(Self::SplitterHorizontal),
-            _ => Err(ParseErr),
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Direction {
     North,
@@ -137,29 +129,25 @@ fn run_mirrors_inner(map: &Array2<Tile>, pos: &Pos, cache: &mut Array3<bool>) {
         });
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay16 {
     map: Array2<Tile>,
 }
 
 impl AocDay<usize, usize> for AocDay16 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut lines = lines.peekable();
-        let width = lines.peek().expect("map does not have any rows").len();
+        let width = lines
+            .peek()
+            .ok_or(DayError::GenericParseErr("map does not have any rows"))?
+            .len();
         let map: Vec<Tile> = lines
-            .flat_map(|row| {
-                row.chars()
-                    .map(|c| c.try_into().expect("failed to parse character in map"))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+            .flat_map(|row| row.chars().map(Tile::try_from).collect::<Vec<_>>())
+            .collect::<Result<_, _>>()?;
 
-        AocDay16 {
+        Ok(AocDay16 {
             map: Array2::from_shape_vec((map.len() / width, width), map)
-                .expect("failed to build array2"),
-        }
+                .map_err(|_| DayError::GenericParseErr("map rows must have the same length"))?,
+        })
     }
     fn part1(&self) -> usize {
         run_mirrors(
@@ -239,14 +227,16 @@ mod day16tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay16::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay16::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 46);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay16::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay16::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 51);
+        Ok(())
     }
 }
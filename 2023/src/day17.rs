@@ -1,99 +1,10 @@
 use ndarray::Array2;
-use pathfinding::prelude::astar;
 
-use crate::AocDay;
-
-fn get_possible_next_positions<const MAX_STRAIGHT: usize, const MIN_STRAIGHT: usize>(
-    pos: &Pos,
-    map: &Array2<u32>,
-) -> Vec<(Pos, u32)> {
-    Direction::get_all()
-        .into_iter()
-        .filter(|d| *d != pos.direction.get_opposite())
-        .filter(|d| pos.straight_line_count < MAX_STRAIGHT || pos.direction != *d)
-        // if straight_line_count == 0, we're in the start, so allow any direction
-        .filter(|d| {
-            pos.straight_line_count == 0
-                || pos.straight_line_count >= MIN_STRAIGHT
-                || pos.direction == *d
-        })
-        .filter_map(|d| {
-            if pos.direction == d {
-                pos.move_pos(map.shape())
-            } else {
-                let new_pos = Pos {
-                    x: pos.x,
-                    y: pos.y,
-                    straight_line_count: 0,
-                    direction: d,
-                };
-                new_pos.move_pos(map.shape())
-            }
-        })
-        .map(|p| {
-            let cost = map[(p.y, p.x)];
-            (p, cost)
-        })
-        .collect()
-}
-
-fn heuristic_fun(pos: &Pos, map: &Array2<u32>) -> u32 {
-    (pos.x.abs_diff(map.ncols() - 1) + pos.y.abs_diff(map.nrows() - 1)) as u32
-}
-
-#[derive(Debug, Hash, Clone, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Direction {
-    fn get_all() -> Vec<Self> {
-        vec![
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ]
-    }
-    fn get_opposite(&self) -> Self {
-        match *self {
-            Self::North => Self::South,
-            Self::East => Self::West,
-            Self::South => Self::North,
-            Self::West => Self::East,
-        }
-    }
-}
-
-#[derive(Debug, Hash, Clone, PartialEq, Eq)]
-struct Pos {
-    x: usize,
-    y: usize,
-    straight_line_count: usize,
-    direction: Direction,
-}
-
-impl Pos {
-    fn move_pos(&self, limits: &[usize]) -> Option<Pos> {
-        let (x, y) = (self.x, self.y);
-        match self.direction {
-            Direction::North => y.checked_sub(1).map(|y| (x, y)),
-            Direction::East => x.checked_add(1).map(|x| (x, y)),
-            Direction::South => y.checked_add(1).map(|y| (x, y)),
-            Direction::West => x.checked_sub(1).map(|x| (x, y)),
-        }
-        .filter(|(x, y)| *x < limits[1] && *y < limits[0])
-        .map(|(x, y)| Pos {
-            x,
-            y,
-            straight_line_count: self.straight_line_count + 1,
-            direction: self.direction.clone(),
-        })
-    }
-}
+use aoc_common::{
+    navigation::{Bounded, Direction, Vec2D},
+    search::{astar, WalkCosts, Walker},
+    AocDay, DayError,
+};
 
 pub struct AocDay17 {
     map: Array2<u32>,
@@ -101,47 +12,53 @@ pub struct AocDay17 {
 
 impl AocDay17 {
     fn solve<const MAX_STRAIGHT: usize, const MIN_STRAIGHT: usize>(&self) -> u32 {
-        let start = Pos {
-            x: 0,
-            y: 0,
-            straight_line_count: 0,
-            direction: Direction::East,
+        let costs = WalkCosts {
+            turn: 0,
+            turn_in_place: false,
+            min_run: MIN_STRAIGHT,
+            max_run: MAX_STRAIGHT,
         };
-        let (path, _steps) = astar(
-            &start,
-            |pos| get_possible_next_positions::<MAX_STRAIGHT, MIN_STRAIGHT>(pos, &self.map),
-            |pos| heuristic_fun(pos, &self.map),
-            |pos| {
-                pos.x == self.map.ncols() - 1
-                    && pos.y == self.map.nrows() - 1
-                    && pos.straight_line_count >= MIN_STRAIGHT
-            },
+        let topology = Bounded::new(Vec2D::new(self.map.ncols(), self.map.nrows()));
+        let end = Vec2D::new(self.map.ncols() - 1, self.map.nrows() - 1);
+
+        // the crucible can leave the start in any direction
+        let starts = [Direction::East, Direction::South]
+            .map(|direction| Walker::new(Vec2D::new(0, 0), direction));
+        let (_, heat_loss) = astar(
+            starts,
+            |walker| costs.moves(walker, &topology, |pos| Some(self.map[(pos.y, pos.x)])),
+            |walker| walker.pos.manhattan_distance(&end) as u32,
+            |walker| walker.pos == end && walker.run >= MIN_STRAIGHT,
         )
         .expect("no goal reached");
 
-        path.into_iter()
-            .skip(1)
-            .map(|pos| self.map[(pos.y, pos.x)])
-            .sum()
+        heat_loss
     }
 }
 
 impl AocDay<u32, u32> for AocDay17 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut lines = lines.peekable();
-        let width = lines.peek().expect("map does not have any rows").len();
+        let width = lines
+            .peek()
+            .ok_or(DayError::GenericParseErr("map does not have any rows"))?
+            .len();
         let map: Vec<u32> = lines
             .flat_map(|row| {
                 row.chars()
-                    .map(|c| c.to_digit(10).expect("failed to parse character in map"))
+                    .map(|c| {
+                        c.to_digit(10).ok_or(DayError::GenericParseErr(
+                            "failed to parse character in map",
+                        ))
+                    })
                     .collect::<Vec<_>>()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        AocDay17 {
+        Ok(AocDay17 {
             map: Array2::from_shape_vec((map.len() / width, width), map)
-                .expect("failed to build array2"),
-        }
+                .map_err(|_| DayError::GenericParseErr("map rows must have the same length"))?,
+        })
     }
     fn part1(&self) -> u32 {
         self.solve::<3, 0>()
@@ -179,16 +96,18 @@ mod day17tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay17::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay17::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 102);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay17::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay17::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 94);
-        let day = AocDay17::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+        let day = AocDay17::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part2(), 71);
+        Ok(())
     }
 }
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
-use crate::AocDay;
+use aoc_common::{interval_set::IntervalSet, parsing::numbered_lines, AocDay, DayError};
+use aoc_common_macros::TryFromChar;
 
 #[derive(Clone)]
 struct Piece<T> {
@@ -10,7 +11,7 @@ struct Piece<T> {
     s: T,
 }
 
-type PieceRanges = Piece<Option<Range>>;
+type PieceRanges = Piece<IntervalSet<u32>>;
 
 impl<T: Clone> Piece<T> {
     fn new(init: T) -> Self {
@@ -52,24 +53,31 @@ impl PieceRanges {
     fn get_distinct_combinations(&self) -> u64 {
         [&self.x, &self.m, &self.a, &self.s]
             .iter()
-            .map(|attribute| attribute.as_ref().map(Range::count).unwrap_or(0))
+            .map(|attribute| attribute.len() as u64)
             .product()
     }
 }
 
 impl FromStr for Piece<u32> {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s
             .strip_prefix('{')
             .and_then(|s| s.strip_suffix('}'))
-            .ok_or(ParseErr)?;
+            .ok_or(DayError::GenericParseErr("piece must be surrounded by {}"))?;
 
         let mut piece = Self::new(0);
         for attribute in s.split(',') {
-            let (field, value) = attribute.split_once('=').ok_or(ParseErr)?;
-            let field = field.chars().nth(0).ok_or(ParseErr)?.try_into()?;
-            let value = value.parse().map_err(|_| ParseErr)?;
+            let (field, value) = attribute
+                .split_once('=')
+                .ok_or(DayError::GenericParseErr("attribute must contain ="))?;
+            let field = Field::try_from(
+                field
+                    .chars()
+                    .next()
+                    .ok_or(DayError::GenericParseErr("attribute must have a name"))?,
+            )?;
+            let value = value.parse()?;
 
             piece.set(&field, value);
         }
@@ -78,26 +86,18 @@ impl FromStr for Piece<u32> {
     }
 }
 
+#[derive(TryFromChar)]
 enum Field {
+    #[char_repr = 'x']
     XCoolLooking,
+    #[char_repr = 'm']
     Musical,
+    #[char_repr = 'a']
     Aerodynamic,
+    #[char_repr = 's']
     Shiny,
 }
 
-impl TryFrom<char> for Field {
-    type Error = ParseErr;
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'x' => Ok(Self::XCoolLooking),
-            'm' => Ok(Self::Musical),
-            'a' => Ok(Self::Aerodynamic),
-            's' => Ok(Self::Shiny),
-            _ => Err(ParseErr),
-        }
-    }
-}
-
 struct Step {
     cond: Option<(Field, Cond)>,
     outcome: Outcome,
@@ -115,26 +115,33 @@ impl Step {
     // (pass, fail)
     fn test_piece_ranges(&self, mut piece: PieceRanges) -> (PieceRanges, PieceRanges) {
         if let Some((field, condition)) = &self.cond {
-            let pass = condition.test_range(piece.get(field));
-            let fail = condition.test_range_neg(piece.get(field));
+            let (pass, fail) = condition.split(piece.get(field));
 
             let mut pass_piece = piece.clone();
             pass_piece.set(field, pass);
             piece.set(field, fail);
             (pass_piece, piece)
         } else {
-            (piece, PieceRanges::new(None))
+            (piece, PieceRanges::new(IntervalSet::new()))
         }
     }
 }
 
 impl FromStr for Step {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.split_once(':') {
             Some((condition, outcome)) => {
-                let field = condition.chars().nth(0).ok_or(ParseErr)?.try_into()?;
-                let condition = condition.get(1..).ok_or(ParseErr)?.parse()?;
+                let field = Field::try_from(
+                    condition
+                        .chars()
+                        .next()
+                        .ok_or(DayError::GenericParseErr("condition must have a field"))?,
+                )?;
+                let condition = condition
+                    .get(1..)
+                    .ok_or(DayError::GenericParseErr("condition must have an operator"))?
+                    .parse()?;
 
                 Ok(Step {
                     cond: Some((field, condition)),
@@ -162,45 +169,26 @@ impl Cond {
             Cond::Gt(v) => other > *v,
         }
     }
-    fn test_range(&self, other: &Option<Range>) -> Option<Range> {
-        match self {
-            Cond::Lt(v) => other.clone().and_then(|range| {
-                if range.min >= *v {
-                    None
-                } else {
-                    Some(Range {
-                        min: range.min,
-                        max: range.max.min(*v - 1),
-                    })
-                }
-            }),
-            Cond::Gt(v) => other.clone().and_then(|range| {
-                if range.max <= *v {
-                    None
-                } else {
-                    Some(Range {
-                        min: range.min.max(*v + 1),
-                        max: range.max,
-                    })
-                }
-            }),
-        }
-    }
-    fn test_range_neg(&self, other: &Option<Range>) -> Option<Range> {
+    /// Split a range into the values that pass the condition and the ones
+    /// that fail it.
+    fn split(&self, other: &IntervalSet<u32>) -> (IntervalSet<u32>, IntervalSet<u32>) {
         match self {
-            Cond::Lt(v) => Cond::Gt(v - 1).test_range(other),
-            Cond::Gt(v) => Cond::Lt(v + 1).test_range(other),
+            Cond::Lt(v) => other.split_at(*v),
+            Cond::Gt(v) => {
+                let (fail, pass) = other.split_at(*v + 1);
+                (pass, fail)
+            }
         }
     }
 }
 
 impl FromStr for Cond {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.chars().nth(0) {
-            Some('>') => Ok(Self::Gt(s[1..].parse().map_err(|_| ParseErr)?)),
-            Some('<') => Ok(Self::Lt(s[1..].parse().map_err(|_| ParseErr)?)),
-            _ => Err(ParseErr),
+            Some('>') => Ok(Self::Gt(s[1..].parse()?)),
+            Some('<') => Ok(Self::Lt(s[1..].parse()?)),
+            _ => Err(DayError::GenericParseErr("unknown condition operator")),
         }
     }
 }
@@ -212,7 +200,7 @@ enum Outcome {
 }
 
 impl FromStr for Outcome {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "A" => Ok(Self::Accept),
@@ -222,21 +210,6 @@ impl FromStr for Outcome {
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
-#[derive(Debug, Clone)]
-struct Range {
-    min: u32, // inclusive
-    max: u32, // inclusive
-}
-
-impl Range {
-    fn count(&self) -> u64 {
-        u64::from(self.min.abs_diff(self.max)) + 1
-    }
-}
-
 pub struct AocDay19 {
     rules: HashMap<String, Vec<Step>>,
     pieces: Vec<Piece<u32>>,
@@ -264,28 +237,29 @@ impl AocDay19 {
 }
 
 impl AocDay<u32, u64> for AocDay19 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let mut lines = numbered_lines(lines);
         let rules = lines
             .by_ref()
-            .take_while(|s| !s.is_empty())
+            .take_while(|line| !line.text.is_empty())
             .map(|line| {
-                let (name, rules) = line
-                    .split_once('{')
-                    .expect("failed to parse workflow: doesn't contain any rules");
-                let rules = rules
-                    .strip_suffix('}')
-                    .expect("failed to parse workflow: doesn't contain any rules")
-                    .split(',')
-                    .map(|rule| rule.parse().expect("failed to parse rule"))
-                    .collect();
-                (name.to_string(), rules)
+                line.parse_with(|line| {
+                    let no_rules =
+                        || DayError::GenericParseErr("workflow doesn't contain any rules");
+                    let (name, rules) = line.split_once('{').ok_or_else(no_rules)?;
+                    let rules = rules
+                        .strip_suffix('}')
+                        .ok_or_else(no_rules)?
+                        .split(',')
+                        .map(|rule| rule.parse())
+                        .collect::<Result<_, _>>()?;
+                    Ok::<_, DayError>((name.to_string(), rules))
+                })
             })
-            .collect();
-        let pieces = lines
-            .map(|line| line.parse().expect("failed to parse piece"))
-            .collect();
+            .collect::<Result<_, _>>()?;
+        let pieces = lines.map(|line| line.parse()).collect::<Result<_, _>>()?;
 
-        AocDay19 { rules, pieces }
+        Ok(AocDay19 { rules, pieces })
     }
     fn part1(&self) -> u32 {
         self.pieces
@@ -313,7 +287,7 @@ impl AocDay<u32, u64> for AocDay19 {
     }
     fn part2(&self) -> u64 {
         self.count_possible(
-            PieceRanges::new(Some(Range { min: 1, max: 4000 })),
+            PieceRanges::new(IntervalSet::from(1..=4000)),
             "in".to_string(),
         )
     }
@@ -344,14 +318,16 @@ mod day19tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay19::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay19::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 19114);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay19::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay19::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 167409079868000);
+        Ok(())
     }
 }
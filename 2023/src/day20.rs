@@ -2,7 +2,7 @@ use std::collections::{HashMap, VecDeque};
 
 use num::integer::lcm;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(Debug, PartialEq, Eq)]
 enum ModuleType {
@@ -149,11 +149,13 @@ impl AocDay20 {
 }
 
 impl AocDay<u32, u64> for AocDay20 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut backlink_connections: HashMap<String, Vec<String>> = HashMap::new();
         let modules = lines
             .map(|line| {
-                let (name, connections) = line.split_once(" -> ").unwrap();
+                let (name, connections) = line
+                    .split_once(" -> ")
+                    .ok_or(DayError::GenericParseErr("module must contain ->"))?;
                 let mtype = if name == "broadcaster" {
                     ModuleType::Start
                 } else if name.starts_with('%') {
@@ -161,7 +163,7 @@ impl AocDay<u32, u64> for AocDay20 {
                 } else if name.starts_with('&') {
                     ModuleType::Conjunction
                 } else {
-                    unreachable!("unknown module");
+                    return Err(DayError::GenericParseErr("unknown module"));
                 };
 
                 let name = name.replace(['%', '&'], "");
@@ -177,14 +179,14 @@ impl AocDay<u32, u64> for AocDay20 {
                         .push(name.clone());
                 }
 
-                (name, Module { mtype, connections })
+                Ok((name, Module { mtype, connections }))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        AocDay20 {
+        Ok(AocDay20 {
             modules,
             backlink_connections,
-        }
+        })
     }
     fn part1(&self) -> u32 {
         let mut low_count = 0;
@@ -259,10 +261,11 @@ mod day20tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay20::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay20::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 32000000);
-        let day = AocDay20::preprocessing(INPUT2.iter().map(|x| String::from(*x)));
+        let day = AocDay20::preprocessing_tests(INPUT2)?;
         assert_eq!(day.part1(), 11687500);
+        Ok(())
     }
 }
@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use ndarray::Array2;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 #[derive(PartialEq, Eq)]
 enum Tile {
@@ -11,19 +11,16 @@ enum Tile {
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ParseErr;
+    type Error = DayError;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' | 'S' => Ok(Self::Garden),
             '#' => Ok(Self::Rock),
-            _ => Err(ParseErr),
+            _ => Err(DayError::TryFromCharErr("Tile")),
         }
     }
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
 enum Direction {
     North,
@@ -152,7 +149,7 @@ impl AocDay21 {
             count
         } else {
             let mut count = (remaining_tiles - 1) / 2;
-            if remaining_tiles % 2 == 0 && (dist + tile_size) % 2 == steps % 2 {
+            if remaining_tiles.is_multiple_of(2) && (dist + tile_size) % 2 == steps % 2 {
                 count += 1;
             }
             if max_dist <= steps && max_dist % 2 == steps % 2 {
@@ -200,10 +197,13 @@ impl AocDay21 {
 }
 
 impl AocDay<usize, usize> for AocDay21 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut start = None;
         let mut lines = lines.peekable();
-        let width = lines.peek().expect("map does not have any rows").len();
+        let width = lines
+            .peek()
+            .ok_or(DayError::GenericParseErr("map does not have any rows"))?
+            .len();
         let map: Vec<Tile> = lines
             .enumerate()
             .flat_map(|(y, row)| {
@@ -213,19 +213,19 @@ impl AocDay<usize, usize> for AocDay21 {
                         if c == 'S' {
                             start = Some(Pos { x, y })
                         }
-                        c.try_into().expect("unknown tile type")
+                        Tile::try_from(c)
                     })
                     .collect::<Vec<_>>()
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         let map = Array2::from_shape_vec((map.len() / width, width), map)
-            .expect("failed to build array2");
+            .map_err(|_| DayError::GenericParseErr("map rows must have the same length"))?;
 
-        AocDay21 {
+        Ok(AocDay21 {
             map,
-            start: start.unwrap(),
-        }
+            start: start.ok_or(DayError::GenericParseErr("cannot find start position"))?,
+        })
     }
     fn part1(&self) -> usize {
         self.simulate(64)
@@ -254,19 +254,21 @@ mod day21tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay21::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay21::preprocessing_tests(INPUT)?;
         assert_eq!(day.simulate(6), 16);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay21::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay21::preprocessing_tests(INPUT)?;
         assert_eq!(day.simulate(10), 50);
         assert_eq!(day.simulate(50), 1594);
         assert_eq!(day.simulate(100), 6536);
         assert_eq!(day.simulate(500), 167004);
         assert_eq!(day.simulate(1000), 668697);
         assert_eq!(day.simulate(5000), 16733044);
+        Ok(())
     }
 }
@@ -1,62 +1,24 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use ndarray::Array3;
 
-use crate::AocDay;
+use aoc_common::{aa_box::AaBox, navigation::Vec3D, AocDay, DayError};
 
-#[derive(Debug, Clone)]
-struct Brick {
-    start: Pos3D,
-    end: Pos3D,
-}
-
-impl FromStr for Brick {
-    type Err = ParseErr;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once('~').unwrap();
-
-        Ok(Brick {
-            start: start.parse()?,
-            end: end.parse()?,
-        })
-    }
-}
-
-impl Brick {
-    fn get_pos(&self) -> impl Iterator<Item = Pos3D> + '_ {
-        (self.start.x..=self.end.x).flat_map(move |x| {
-            (self.start.y..=self.end.y)
-                .flat_map(move |y| (self.start.z..=self.end.z).map(move |z| Pos3D { x, y, z }))
-        })
-    }
-}
+type Brick = AaBox<usize, 3>;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Pos3D {
-    x: usize,
-    y: usize,
-    z: usize,
-}
+fn parse_brick(s: &str) -> Result<Brick, DayError> {
+    let (start, end) = s
+        .split_once('~')
+        .ok_or(DayError::GenericParseErr("brick must contain ~"))?;
+    let (start, end): (Vec3D<usize>, Vec3D<usize>) = (start.parse()?, end.parse()?);
 
-impl FromStr for Pos3D {
-    type Err = ParseErr;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut coords = s
-            .split(',')
-            .take(3)
-            .map(|num| num.parse().map_err(|_| ParseErr));
-        Ok(Pos3D {
-            x: coords.next().ok_or(ParseErr)??,
-            y: coords.next().ok_or(ParseErr)??,
-            z: coords.next().ok_or(ParseErr)??,
-        })
-    }
+    Ok(Brick::from_corners(
+        [start.x, start.y, start.z],
+        [end.x, end.y, end.z],
+    ))
 }
 
-#[derive(Debug)]
-struct ParseErr;
-
 fn simulate_fall(
     sorted_bricks: &[Brick],
     dimensions: (usize, usize, usize),
@@ -72,9 +34,9 @@ fn simulate_fall(
         }
         let mut offset: usize = 1;
 
-        'outer: while brick.start.z.checked_sub(offset).is_some() {
-            for pos in brick.get_pos() {
-                if brick_map[(pos.x, pos.y, pos.z - offset)].is_some() {
+        'outer: while brick.min[2].checked_sub(offset).is_some() {
+            for [x, y, z] in brick.points() {
+                if brick_map[(x, y, z - offset)].is_some() {
                     break 'outer;
                 }
             }
@@ -86,21 +48,12 @@ fn simulate_fall(
             changed_count += 1;
         }
 
-        let new_brick = Brick {
-            start: Pos3D {
-                x: brick.start.x,
-                y: brick.start.y,
-                z: brick.start.z - offset,
-            },
-            end: Pos3D {
-                x: brick.end.x,
-                y: brick.end.y,
-                z: brick.end.z - offset,
-            },
-        };
+        let mut new_brick = *brick;
+        new_brick.min[2] -= offset;
+        new_brick.max[2] -= offset;
 
-        new_brick.get_pos().for_each(|pos| {
-            brick_map[(pos.x, pos.y, pos.z)] = Some(new_bricks.len());
+        new_brick.points().for_each(|[x, y, z]| {
+            brick_map[(x, y, z)] = Some(new_bricks.len());
         });
         new_bricks.push(new_brick);
     }
@@ -119,18 +72,10 @@ fn get_adjacent_bricks_map(
         .iter()
         .enumerate()
         .map(|(i, brick)| {
-            let brick_pos = brick.get_pos();
-
-            let adjacent = brick_pos
-                .into_iter()
-                .filter_map(|pos| {
-                    z_change_fn(pos.z).map(|z| Pos3D {
-                        x: pos.x,
-                        y: pos.y,
-                        z,
-                    })
-                })
-                .filter_map(|pos| brick_map[(pos.x, pos.y, pos.z)]) // make sure resulting position is a brick
+            let adjacent = brick
+                .points()
+                .filter_map(|[x, y, z]| z_change_fn(z).map(|z| (x, y, z)))
+                .filter_map(|pos| *brick_map.get(pos)?) // make sure resulting position is a brick
                 .filter(|j| i != *j) // don't add itself
                 .sorted()
                 .dedup()
@@ -148,33 +93,21 @@ pub struct AocDay22 {
 }
 
 impl AocDay<usize, usize> for AocDay22 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut bricks: Vec<Brick> = lines
-            .map(|line| line.parse().expect("invalid brick"))
-            .collect();
+            .map(|line| parse_brick(&line))
+            .collect::<Result<_, _>>()?;
 
-        bricks.sort_by_key(|pos| pos.start.z);
+        bricks.sort_by_key(|brick| brick.min[2]);
 
-        let dimensions = (
-            bricks
-                .iter()
-                .map(|brick| brick.start.x.max(brick.end.x))
-                .max()
-                .unwrap_or(0)
-                + 1,
+        let dimension = |axis: usize| {
             bricks
                 .iter()
-                .map(|brick| brick.start.y.max(brick.end.y))
+                .map(|brick| brick.max[axis])
                 .max()
                 .unwrap_or(0)
-                + 1,
-            bricks
-                .iter()
-                .map(|brick| brick.start.z.max(brick.end.z))
-                .max()
-                .unwrap_or(0)
-                + 1,
-        );
+        };
+        let dimensions = (dimension(0), dimension(1), dimension(2));
 
         let (new_bricks, brick_map, _) = simulate_fall(&bricks, dimensions, None);
 
@@ -198,11 +131,11 @@ impl AocDay<usize, usize> for AocDay22 {
             })
             .collect();
 
-        AocDay22 {
+        Ok(AocDay22 {
             bricks: new_bricks,
             safe_to_disintegrate,
             dimensions,
-        }
+        })
     }
     fn part1(&self) -> usize {
         self.safe_to_disintegrate.iter().filter(|b| **b).count()
@@ -236,14 +169,16 @@ mod day22tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay22::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay22::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 5);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay22::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay22::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 7);
+        Ok(())
     }
 }
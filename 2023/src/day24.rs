@@ -2,21 +2,23 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 use z3::{
-    ast::{Ast, Int, Real},
-    Config, Context, SatResult, Solver,
+    ast::{Int, Real},
+    SatResult, Solver,
 };
 
-use crate::AocDay;
+use aoc_common::{navigation::Vec3D, parsing::parse_lines, AocDay, DayError};
 
 struct Hailstone {
-    position: Pos3D,
-    velocity: Pos3D,
+    position: Vec3D<i64>,
+    velocity: Vec3D<i64>,
 }
 
 impl FromStr for Hailstone {
-    type Err = ParseErr;
+    type Err = DayError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (pos, vel) = s.split_once(" @ ").ok_or(ParseErr)?;
+        let (pos, vel) = s
+            .split_once(" @ ")
+            .ok_or(DayError::GenericParseErr("hailstone must contain @"))?;
         Ok(Hailstone {
             position: pos.parse()?,
             velocity: vel.parse()?,
@@ -24,28 +26,6 @@ impl FromStr for Hailstone {
     }
 }
 
-struct Pos3D {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl FromStr for Pos3D {
-    type Err = ParseErr;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut coords = s.split(',').map(|c| c.trim().parse().map_err(|_| ParseErr));
-
-        Ok(Pos3D {
-            x: coords.next().ok_or(ParseErr)??,
-            y: coords.next().ok_or(ParseErr)??,
-            z: coords.next().ok_or(ParseErr)??,
-        })
-    }
-}
-
-#[derive(Debug)]
-struct ParseErr;
-
 pub struct AocDay24 {
     hailstones: Vec<Hailstone>,
 }
@@ -93,56 +73,51 @@ impl AocDay24 {
 }
 
 impl AocDay<usize, i64> for AocDay24 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
-        let hailstones = lines
-            .map(|line| line.parse().expect("invalid hailstone"))
-            .collect();
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let hailstones = parse_lines(lines)?;
 
-        AocDay24 { hailstones }
+        Ok(AocDay24 { hailstones })
     }
     fn part1(&self) -> usize {
         self.count_intersections((200000000000000, 400000000000000))
     }
     fn part2(&self) -> i64 {
-        let cfg = Config::new();
-        let ctx = Context::new(&cfg);
-        let rock_px = Real::new_const(&ctx, "rpx");
-        let rock_py = Real::new_const(&ctx, "rpy");
-        let rock_pz = Real::new_const(&ctx, "rpz");
-        let rock_vx = Real::new_const(&ctx, "rvx");
-        let rock_vy = Real::new_const(&ctx, "rvy");
-        let rock_vz = Real::new_const(&ctx, "rvz");
-
-        let solver = Solver::new(&ctx);
-
-        let zero = Real::from_int(&Int::from_i64(&ctx, 0));
+        let rock_px = Real::new_const("rpx");
+        let rock_py = Real::new_const("rpy");
+        let rock_pz = Real::new_const("rpz");
+        let rock_vx = Real::new_const("rvx");
+        let rock_vy = Real::new_const("rvy");
+        let rock_vz = Real::new_const("rvz");
+
+        let solver = Solver::new();
+
+        let zero = Real::from_int(&Int::from_i64(0));
         for (i, hailstone) in self.hailstones.iter().enumerate() {
-            let hailstone_px = Real::from_int(&Int::from_i64(&ctx, hailstone.position.x));
-            let hailstone_py = Real::from_int(&Int::from_i64(&ctx, hailstone.position.y));
-            let hailstone_pz = Real::from_int(&Int::from_i64(&ctx, hailstone.position.z));
-            let hailstone_vx = Real::from_int(&Int::from_i64(&ctx, hailstone.velocity.x));
-            let hailstone_vy = Real::from_int(&Int::from_i64(&ctx, hailstone.velocity.y));
-            let hailstone_vz = Real::from_int(&Int::from_i64(&ctx, hailstone.velocity.z));
-            let impact_t = Real::new_const(&ctx, format!("t{}", i));
-            solver.assert(&impact_t.ge(&zero));
+            let hailstone_px = Real::from_int(&Int::from_i64(hailstone.position.x));
+            let hailstone_py = Real::from_int(&Int::from_i64(hailstone.position.y));
+            let hailstone_pz = Real::from_int(&Int::from_i64(hailstone.position.z));
+            let hailstone_vx = Real::from_int(&Int::from_i64(hailstone.velocity.x));
+            let hailstone_vy = Real::from_int(&Int::from_i64(hailstone.velocity.y));
+            let hailstone_vz = Real::from_int(&Int::from_i64(hailstone.velocity.z));
+            let impact_t = Real::new_const(format!("t{}", i));
+            solver.assert(&impact_t.ge(zero.clone()));
             solver.assert(
-                &(&hailstone_px + &hailstone_vx * &impact_t)
-                    ._eq(&(&rock_px + &rock_vx * &impact_t)),
+                &(&hailstone_px + &hailstone_vx * &impact_t).eq(&rock_px + &rock_vx * &impact_t),
             );
             solver.assert(
-                &(&hailstone_py + &hailstone_vy * &impact_t)
-                    ._eq(&(&rock_py + &rock_vy * &impact_t)),
+                &(&hailstone_py + &hailstone_vy * &impact_t).eq(&rock_py + &rock_vy * &impact_t),
             );
             solver.assert(
-                &(&hailstone_pz + &hailstone_vz * &impact_t)
-                    ._eq(&(&rock_pz + &rock_vz * &impact_t)),
+                &(&hailstone_pz + &hailstone_vz * &impact_t).eq(&rock_pz + &rock_vz * &impact_t),
             );
         }
 
         assert_eq!(solver.check(), SatResult::Sat);
         let model = solver.get_model().unwrap();
-        let result = model.eval(&(&rock_px + &rock_py + &rock_pz), true).unwrap();
-        result.as_real().unwrap().0
+        let result = model
+            .eval(&(&rock_px + &rock_py + &rock_pz).to_int(), true)
+            .unwrap();
+        result.as_i64().unwrap()
     }
 }
 
@@ -159,14 +134,16 @@ mod day24tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay24::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay24::preprocessing_tests(INPUT)?;
         assert_eq!(day.count_intersections((7, 27)), 2);
+        Ok(())
     }
 
     #[test]
-    fn part2() {
-        let day = AocDay24::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part2() -> Result<(), DayError> {
+        let day = AocDay24::preprocessing_tests(INPUT)?;
         assert_eq!(day.part2(), 47);
+        Ok(())
     }
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use ndarray::Array2;
 
-use crate::AocDay;
+use aoc_common::{AocDay, DayError};
 
 // Stoer–Wagner algorithm
 fn find_global_min_cut(mut edges: Array2<i32>) -> usize {
@@ -48,13 +48,15 @@ pub struct AocDay25 {
 }
 
 impl AocDay<usize, String> for AocDay25 {
-    fn preprocessing(lines: impl Iterator<Item = String>) -> Self {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
         let mut i = 0;
         let mut conversion: HashMap<String, usize> = HashMap::new();
         let mut edges_list: Vec<(usize, usize)> = Vec::new();
 
         for line in lines {
-            let (from, to_list) = line.split_once(": ").unwrap();
+            let (from, to_list) = line
+                .split_once(": ")
+                .ok_or(DayError::GenericParseErr("line must contain :"))?;
             let from_i = *conversion.entry(from.to_string()).or_insert_with(|| {
                 i += 1;
                 i - 1
@@ -75,7 +77,7 @@ impl AocDay<usize, String> for AocDay25 {
             edges[(edge.1, edge.0)] = 1;
         }
 
-        AocDay25 { edges }
+        Ok(AocDay25 { edges })
     }
     fn part1(&self) -> usize {
         let group_size = find_global_min_cut(self.edges.clone());
@@ -107,8 +109,9 @@ mod day25tests {
     ];
 
     #[test]
-    fn part1() {
-        let day = AocDay25::preprocessing(INPUT.iter().map(|x| String::from(*x)));
+    fn part1() -> Result<(), DayError> {
+        let day = AocDay25::preprocessing_tests(INPUT)?;
         assert_eq!(day.part1(), 54);
+        Ok(())
     }
 }
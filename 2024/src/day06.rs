@@ -1,31 +1,11 @@
 use aoc_common::{
-    navigation::{Direction, Vec2D, VecSum},
+    navigation::{Direction, DirectionSet, Vec2D, VecSum},
     parsing::try_parse_2d_array,
     AocDay, DayError,
 };
 use aoc_common_macros::TryFromChar;
 use ndarray::Array2;
 
-fn rotate_cw(direction: &Direction) -> Direction {
-    match direction {
-        Direction::North => Direction::East,
-        Direction::East => Direction::South,
-        Direction::South => Direction::West,
-        Direction::West => Direction::North,
-        _ => unreachable!(),
-    }
-}
-
-fn dir_to_u8(direction: &Direction) -> u8 {
-    match direction {
-        Direction::North => 1 << 0,
-        Direction::East => 1 << 1,
-        Direction::South => 1 << 2,
-        Direction::West => 1 << 3,
-        _ => unreachable!(),
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, TryFromChar)]
 enum Tile {
     #[char_repr = '#']
@@ -54,23 +34,21 @@ fn is_loop(
     map: &Array2<Tile>,
     mut guard_pos: DirectedPos,
     map_size: &Pos,
-    mut seen: Array2<u8>,
+    mut seen: Array2<DirectionSet>,
     new_obstacle: &Pos,
 ) -> bool {
     while let Some(new_pos) = guard_pos
         .pos
-        .vec_sum(&Vec2D::<isize>::from(guard_pos.direction.clone()))
+        .vec_sum(&Vec2D::<isize>::from(guard_pos.direction))
         .and_then(|pos| pos.bind_to_map(map_size))
     {
         if map[&new_pos] == Tile::Obstacle || *new_obstacle == new_pos {
-            guard_pos.direction = rotate_cw(&guard_pos.direction);
+            guard_pos.direction = guard_pos.direction.rotate_cw_90();
         } else {
-            let dir_mask = dir_to_u8(&guard_pos.direction);
-            if seen[&new_pos] & dir_mask != 0 {
+            if !seen[&new_pos].insert(guard_pos.direction) {
                 // already been in this position: loop
                 return true;
             }
-            seen[&new_pos] |= dir_mask;
             guard_pos.pos = new_pos;
         }
     }
@@ -107,11 +85,11 @@ impl AocDay<usize, usize> for AocDay06 {
 
         while let Some(new_pos) = guard_pos
             .pos
-            .vec_sum(&Vec2D::<isize>::from(guard_pos.direction.clone()))
+            .vec_sum(&Vec2D::<isize>::from(guard_pos.direction))
             .and_then(|pos| pos.bind_to_map(&self.map_size))
         {
             if self.map[&new_pos] == Tile::Obstacle {
-                guard_pos.direction = rotate_cw(&guard_pos.direction);
+                guard_pos.direction = guard_pos.direction.rotate_cw_90();
             } else {
                 seen[&new_pos] = true;
                 guard_pos.pos = new_pos;
@@ -121,20 +99,20 @@ impl AocDay<usize, usize> for AocDay06 {
         seen.iter().filter(|b| **b).count()
     }
     fn part2(&self) -> usize {
-        let mut seen = Array2::<u8>::zeros(self.map.dim());
+        let mut seen = Array2::<DirectionSet>::default(self.map.dim());
         let mut guard_pos = self.guard.clone();
         let mut count = 0;
 
         while let Some(new_pos) = guard_pos
             .pos
-            .vec_sum(&Vec2D::<isize>::from(guard_pos.direction.clone()))
+            .vec_sum(&Vec2D::<isize>::from(guard_pos.direction))
             .and_then(|pos| pos.bind_to_map(&self.map_size))
         {
             if self.map[&new_pos] == Tile::Obstacle {
-                guard_pos.direction = rotate_cw(&guard_pos.direction);
+                guard_pos.direction = guard_pos.direction.rotate_cw_90();
             } else {
-                let is_new_pos = seen[&new_pos] == 0;
-                seen[&new_pos] |= dir_to_u8(&guard_pos.direction);
+                let is_new_pos = seen[&new_pos].is_empty();
+                seen[&new_pos].insert(guard_pos.direction);
                 if is_new_pos
                     && is_loop(
                         &self.map,
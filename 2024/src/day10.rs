@@ -1,9 +1,8 @@
 use aoc_common::{
-    navigation::{Direction, Vec2D, VecSum},
-    parsing::try_parse_2d_array,
+    grid::Grid,
+    navigation::{Topology, Vec2D},
     AocDay, DayError,
 };
-use ndarray::Array2;
 use pathfinding::prelude::{count_paths, dfs_reach};
 
 type Pos = Vec2D<usize>;
@@ -25,34 +24,27 @@ impl TryFrom<char> for Tile {
     }
 }
 
-fn get_all_trailheads(map: &Array2<Tile>) -> impl Iterator<Item = Pos> + use<'_> {
-    map.indexed_iter()
+fn get_all_trailheads(map: &Grid<Tile>) -> impl Iterator<Item = Pos> + use<'_> {
+    map.iter()
         .filter(|(_, tile)| tile.height == 0)
-        .map(|((y, x), _)| Pos::new(x, y))
+        .map(|(pos, _)| pos)
 }
 
-fn get_possible_next_positions(
-    map: &Array2<Tile>,
-    pos: Pos,
-) -> impl Iterator<Item = Pos> + use<'_> {
+fn get_possible_next_positions(map: &Grid<Tile>, pos: Pos) -> impl Iterator<Item = Pos> + use<'_> {
     let old_height = map[&pos].height;
-    let map_bounds = Pos::new(map.ncols(), map.nrows());
-    Direction::get_all_orthogonal()
-        .flat_map(move |dir| pos.vec_sum(&Vec2D::<isize>::from(dir)))
-        .flat_map(move |pos| pos.bind_to_map(&map_bounds))
-        .filter(move |new_pos| {
-            let new_height = map[new_pos].height;
-            old_height + 1 == new_height
-        })
+    map.neighbours4(&pos).filter(move |new_pos| {
+        let new_height = map[new_pos].height;
+        old_height + 1 == new_height
+    })
 }
 
 pub struct AocDay10 {
-    map: Array2<Tile>,
+    map: Grid<Tile>,
 }
 
 impl AocDay<usize, usize> for AocDay10 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let map = try_parse_2d_array(lines)?;
+        let map = Grid::parse(lines)?;
         Ok(AocDay10 { map })
     }
     fn part1(&self) -> usize {
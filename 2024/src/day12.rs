@@ -1,6 +1,6 @@
 use aoc_common::{
-    navigation::{Direction, Vec2D, VecSum},
-    parsing::try_parse_2d_array,
+    grid::Grid,
+    navigation::{Direction, DirectionSet, Topology, Vec2D},
     AocDay, DayError,
 };
 use itertools::Itertools;
@@ -9,78 +9,67 @@ use pathfinding::prelude::bfs_reach;
 
 type Pos = Vec2D<usize>;
 
-fn get_possible_next_positions(
-    map: &Array2<char>,
-    pos: Pos,
-) -> impl Iterator<Item = Pos> + use<'_> {
+fn get_possible_next_positions(map: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + use<'_> {
     let region = map[&pos];
-    let map_bounds = Pos::new(map.ncols(), map.nrows());
-    Direction::get_all_orthogonal()
-        .flat_map(move |dir| pos.vec_sum(&Vec2D::<isize>::from(dir)))
-        .flat_map(move |pos| pos.bind_to_map(&map_bounds))
+    map.neighbours4(&pos)
         .filter(move |new_pos| map[new_pos] == region)
 }
 
-fn get_perimeter(map: &Array2<char>, pos: Pos) -> usize {
+fn get_perimeter(map: &Grid<char>, pos: Pos) -> usize {
     Direction::get_all_orthogonal().count() - get_possible_next_positions(map, pos).count()
 }
 
 /// First return value is the delta to apply to the perimeter of the region, while
-/// the second is a mask of the directions that are part of the perimeter.
+/// the second is the set of directions that are part of the perimeter.
 ///
 /// Delta is calculated by counting all directions part of perimeter and subtracting
 /// one per neighbour that also has each direction.
 /// This delta can be negative in the (edge) case that two sides are joined by a tile,
 /// therefore the two separate sides are now a single side.
-fn get_perimeter_mask(map: &Array2<char>, pos: &Pos, visited: &Array2<u8>) -> (isize, u8) {
+fn get_perimeter_sides(
+    map: &Grid<char>,
+    pos: &Pos,
+    visited: &Array2<Option<DirectionSet>>,
+) -> (isize, DirectionSet) {
     let region = map[pos];
-    let map_bounds = Pos::new(map.ncols(), map.nrows());
     Direction::get_all_orthogonal()
         .map(move |dir| {
-            let dir_mask = dir.to_mask();
-            (
-                dir_mask,
-                pos.vec_sum(&Vec2D::<isize>::from(dir))
-                    .and_then(|pos| pos.bind_to_map(&map_bounds).filter(|p| map[p] == region)),
-            )
+            let neighbour = map.step(pos, dir).map(|(p, _)| p);
+            (dir, neighbour.filter(|p| map[p] == region))
         })
-        // sort here so that we can know the full mask before calculating the delta
+        // sort here so that we can know all the sides before calculating the delta
         .sorted_by_key(move |(_, pos)| pos.is_some())
         .fold(
-            (0, 0),
-            |(perimeter_delta, unfiltered_mask), (dir_mask, new_pos)| {
+            (0, DirectionSet::new()),
+            |(perimeter_delta, sides), (dir, new_pos)| {
                 if let Some(new_pos) = new_pos {
-                    (
-                        perimeter_delta
-                            - (unfiltered_mask & visited[&new_pos]).count_ones() as isize,
-                        unfiltered_mask,
-                    )
+                    let shared_sides = sides & visited[&new_pos].unwrap_or_default();
+                    (perimeter_delta - shared_sides.len() as isize, sides)
                 } else {
-                    let new_mask = unfiltered_mask | dir_mask;
-                    (new_mask.count_ones() as isize, new_mask)
+                    let sides = sides | dir;
+                    (sides.len() as isize, sides)
                 }
             },
         )
 }
 
 pub struct AocDay12 {
-    map: Array2<char>,
+    map: Grid<char>,
 }
 
 impl AocDay<usize, isize> for AocDay12 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let map = try_parse_2d_array(lines)?;
+        let map = Grid::parse(lines)?;
 
         Ok(AocDay12 { map })
     }
     fn part1(&self) -> usize {
-        let mut visited = Array2::<bool>::default(self.map.dim());
+        let mut visited = Array2::<bool>::default(self.map.cells().dim());
 
         self.map
-            .indexed_iter()
-            .map(|((y, x), _)| {
-                if !visited[(y, x)] {
-                    let pos = Pos::new(x, y);
+            .iter()
+            .map(|(pos, _)| {
+                if !visited[&pos] {
                     let (area, perimeter) =
                         bfs_reach(pos, |p| get_possible_next_positions(&self.map, p.clone())).fold(
                             (0, 0),
@@ -99,21 +88,19 @@ impl AocDay<usize, isize> for AocDay12 {
             .sum()
     }
     fn part2(&self) -> isize {
-        let mut visited = Array2::<u8>::zeros(self.map.dim());
+        let mut visited = Array2::<Option<DirectionSet>>::default(self.map.cells().dim());
 
         self.map
-            .indexed_iter()
-            .map(|((y, x), _)| {
-                if visited[(y, x)] == 0 {
-                    let pos = Pos::new(x, y);
+            .iter()
+            .map(|(pos, _)| {
+                if visited[&pos].is_none() {
                     let (area, perimeter) =
                         bfs_reach(pos, |p| get_possible_next_positions(&self.map, p.clone())).fold(
                             (0, 0),
                             |(area, perimeter), pos| {
-                                let (perimeter_delta, unfiltered_mask) =
-                                    get_perimeter_mask(&self.map, &pos, &visited);
-                                // add 1 << 7 so that inner positions are marked as visited
-                                visited[&pos] |= (1 << 7) | unfiltered_mask;
+                                let (perimeter_delta, sides) =
+                                    get_perimeter_sides(&self.map, &pos, &visited);
+                                visited[&pos] = Some(sides);
                                 (area + 1, perimeter + perimeter_delta)
                             },
                         );
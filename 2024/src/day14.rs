@@ -1,27 +1,24 @@
-use std::str::FromStr;
+use std::{num::ParseIntError, str::FromStr};
 
 use aoc_common::{
-    navigation::{Vec2D, VecScale, VecSum},
+    navigation::{Toroidal, Vec2D},
+    parsing::parse_lines,
     AocDay, DayError,
 };
-use itertools::Itertools;
 
 const MAP_BOUNDS: Pos = Pos { x: 101, y: 103 };
 
-type Pos = Vec2D<isize>;
+type Pos = Vec2D<usize>;
 
 #[derive(Debug)]
 struct Robot {
     position: Pos,
-    velocity: Pos,
+    velocity: Vec2D<isize>,
 }
 
 impl Robot {
-    fn move_robot(&self, time: isize) -> Pos {
-        self.velocity
-            .vec_scale(time)
-            .and_then(|vel| self.position.vec_sum(&vel))
-            .expect("overflow when moving robot")
+    fn move_robot(&self, time: isize, map: &Toroidal) -> Pos {
+        map.translate(&self.position, &(self.velocity.clone() * time))
     }
 }
 
@@ -29,13 +26,13 @@ impl FromStr for Robot {
     type Err = DayError;
 
     fn from_str(l: &str) -> Result<Self, Self::Err> {
-        fn parse_pos(pos: &str) -> Result<Pos, DayError> {
+        fn parse_vec<T: FromStr<Err = ParseIntError>>(pos: &str) -> Result<Vec2D<T>, DayError> {
             let (x, y) = pos
                 .get(2..)
                 .and_then(|s| s.split_once(','))
                 .ok_or(DayError::GenericParseErr("cannot find comma in position"))?;
 
-            Ok(Pos::new(x.parse()?, y.parse()?))
+            Ok(Vec2D::new(x.parse()?, y.parse()?))
         }
 
         let (pos, vel) = l
@@ -43,8 +40,8 @@ impl FromStr for Robot {
             .ok_or(DayError::GenericParseErr("cannot find space in input line"))?;
 
         Ok(Robot {
-            position: parse_pos(pos)?,
-            velocity: parse_pos(vel)?,
+            position: parse_vec(pos)?,
+            velocity: parse_vec(vel)?,
         })
     }
 }
@@ -73,9 +70,7 @@ pub struct AocDay14 {
 
 impl AocDay<usize, isize> for AocDay14 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let robots = lines
-            .map(|l| l.parse())
-            .process_results(|it| it.collect_vec())?;
+        let robots = parse_lines(lines)?;
 
         Ok(AocDay14 { robots })
     }
@@ -90,12 +85,13 @@ impl AocDay<usize, isize> for AocDay14 {
         // this happens, hinting that the positions are cyclic.
         // Therefore find the instance with lowest value for the heuristic
         // until we are back at the start.
+        let map = Toroidal::new(MAP_BOUNDS);
         (1..)
             .map(|i| {
                 let (score, is_beginning) = self
                     .robots
                     .iter()
-                    .map(|robot| robot.move_robot(i).wrap_out_of_bounds(&MAP_BOUNDS))
+                    .map(|robot| robot.move_robot(i, &map))
                     .enumerate()
                     .fold((0usize, true), |(score, is_beginning), (i, robot)| {
                         let score = score
@@ -115,9 +111,10 @@ impl AocDay<usize, isize> for AocDay14 {
 
 impl AocDay14 {
     fn part1_inner(&self, map_bounds: &Pos) -> usize {
+        let map = Toroidal::new(map_bounds.clone());
         self.robots
             .iter()
-            .map(|robot| robot.move_robot(100).wrap_out_of_bounds(map_bounds))
+            .map(|robot| robot.move_robot(100, &map))
             .fold([0; 4], |mut quadrants, robot| {
                 if let Some(quadrant) = get_quadrant(&robot, map_bounds) {
                     quadrants[quadrant] += 1;
@@ -31,21 +31,9 @@ impl TryFrom<char> for Move {
     type Error = DayError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '^' => Ok(Self {
-                direction: Direction::North,
-            }),
-            '>' => Ok(Self {
-                direction: Direction::East,
-            }),
-            'v' => Ok(Self {
-                direction: Direction::South,
-            }),
-            '<' => Ok(Self {
-                direction: Direction::West,
-            }),
-            _ => Err(DayError::GenericParseErr("unknown character for move")),
-        }
+        Ok(Self {
+            direction: Direction::from_arrow(value)?,
+        })
     }
 }
 
@@ -60,7 +48,7 @@ fn maybe_move<const DRY_RUN: bool>(
     direction: &Direction,
     map: &mut Array2<Tile>,
 ) -> Option<Pos> {
-    let vec: Vec2D<isize> = Vec2D::from(direction.clone());
+    let vec: Vec2D<isize> = Vec2D::from(*direction);
     let destination = from
         .vec_sum(&vec)
         .expect("overflow on vec sum (impossible due to walls)");
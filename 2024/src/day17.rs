@@ -1,9 +1,6 @@
 use aoc_common::{AocDay, DayError};
 use itertools::Itertools;
-use z3::{
-    ast::{Ast, BV},
-    Config, Context, Optimize, SatResult,
-};
+use z3::{ast::BV, Optimize, SatResult};
 
 type LiteralOperand = u8;
 
@@ -137,31 +134,29 @@ impl<'a> ExecutionContext<'a> {
 }
 
 // This assumes there is only one jump instruction at the end :/
-struct Z3ExecutionContext<'a, 'z> {
-    ctx: &'z Context,
-    solver: Optimize<'z>,
+struct Z3ExecutionContext<'a> {
+    solver: Optimize,
 
-    start_a: BV<'z>,
-    registers: [BV<'z>; 3],
+    start_a: BV,
+    registers: [BV; 3],
     ip: usize,
     instructions: &'a [u8],
     output_size: usize,
 }
 
-impl<'a, 'z> Z3ExecutionContext<'a, 'z> {
+impl<'a> Z3ExecutionContext<'a> {
     const INT_LEN: u32 = 64;
 
-    fn new(ctx: &'z Context, registers: [u32; 3], instructions: &'a [u8]) -> Self {
-        let solver = Optimize::new(ctx);
-        let start_a = BV::new_const(ctx, "a", Self::INT_LEN);
+    fn new(registers: [u32; 3], instructions: &'a [u8]) -> Self {
+        let solver = Optimize::new();
+        let start_a = BV::new_const("a", Self::INT_LEN);
 
         let registers = [
             start_a.clone(),
-            BV::from_u64(ctx, registers[1].into(), Self::INT_LEN),
-            BV::from_u64(ctx, registers[2].into(), Self::INT_LEN),
+            BV::from_u64(registers[1].into(), Self::INT_LEN),
+            BV::from_u64(registers[2].into(), Self::INT_LEN),
         ];
         Self {
-            ctx,
             solver,
             start_a,
             registers,
@@ -207,11 +202,8 @@ impl<'a, 'z> Z3ExecutionContext<'a, 'z> {
                 if self.output_size < self.instructions.len() {
                     self.ip = usize::from(*operand);
                 } else {
-                    self.solver.assert(&self.registers[0]._eq(&BV::from_u64(
-                        self.ctx,
-                        0,
-                        Self::INT_LEN,
-                    )));
+                    self.solver
+                        .assert(&self.registers[0].eq(BV::from_u64(0, Self::INT_LEN)));
                 }
             }
             OpCode::BXC => {
@@ -220,8 +212,7 @@ impl<'a, 'z> Z3ExecutionContext<'a, 'z> {
             }
             OpCode::OUT(operand) => {
                 let result = self.resolve_combo(operand) & 0b111u64;
-                self.solver.assert(&result._eq(&BV::from_u64(
-                    self.ctx,
+                self.solver.assert(&result.eq(BV::from_u64(
                     self.instructions[self.output_size].into(),
                     Self::INT_LEN,
                 )));
@@ -230,9 +221,9 @@ impl<'a, 'z> Z3ExecutionContext<'a, 'z> {
         }
     }
 
-    fn resolve_combo(&self, combo: &ComboOperand) -> BV<'z> {
+    fn resolve_combo(&self, combo: &ComboOperand) -> BV {
         match combo {
-            ComboOperand::Literal(v) => BV::from_u64(self.ctx, (*v).into(), Self::INT_LEN),
+            ComboOperand::Literal(v) => BV::from_u64((*v).into(), Self::INT_LEN),
             ComboOperand::Register(i) => self.registers[*i].clone(),
         }
     }
@@ -283,10 +274,7 @@ impl AocDay<String, u64> for AocDay17 {
         execution_context.get_output()
     }
     fn part2(&self) -> u64 {
-        let cfg = Config::new();
-        let ctx = Context::new(&cfg);
-        let mut execution_context =
-            Z3ExecutionContext::new(&ctx, self.registers, &self.instructions);
+        let mut execution_context = Z3ExecutionContext::new(self.registers, &self.instructions);
         execution_context.run();
 
         execution_context.get_a()
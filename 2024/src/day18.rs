@@ -1,29 +1,22 @@
 use aoc_common::{
-    navigation::{Direction, Vec2D, VecSum},
+    grid::Grid,
+    navigation::{Topology, Vec2D},
     AocDay, DayError,
 };
 use itertools::Itertools;
-use ndarray::Array2;
 use pathfinding::prelude::astar;
 
 type Pos = Vec2D<usize>;
 
 fn get_possible_next_positions(
-    map: &Array2<bool>,
+    map: &Grid<bool>,
     pos: Pos,
 ) -> impl Iterator<Item = (Pos, usize)> + use<'_> {
-    let map_bounds = Pos::new(map.ncols(), map.nrows());
-    Direction::get_all_orthogonal()
-        .flat_map(move |dir| pos.vec_sum(&Vec2D::<isize>::from(dir)))
-        .flat_map(move |pos| pos.bind_to_map(&map_bounds))
+    map.neighbours4(&pos)
         .filter(move |new_pos| !map[new_pos])
         .map(|pos| (pos, 1))
 }
 
-fn pos_dist(from: &Pos, to: &Pos) -> usize {
-    from.x.abs_diff(to.x) + from.y.abs_diff(to.y)
-}
-
 pub struct AocDay18 {
     bytes: Vec<Pos>,
 }
@@ -52,7 +45,7 @@ impl AocDay<usize, String> for AocDay18 {
 
 impl AocDay18 {
     fn part1_inner<const MAP_SIZE: usize, const BYTE_COUNT: usize>(&self) -> usize {
-        let mut map = Array2::default((MAP_SIZE + 1, MAP_SIZE + 1));
+        let mut map = Grid::from_elem(Pos::new(MAP_SIZE + 1, MAP_SIZE + 1), false);
         self.bytes
             .iter()
             .take(BYTE_COUNT)
@@ -63,7 +56,7 @@ impl AocDay18 {
         let (_, cost) = astar(
             &start_pos,
             |pos| get_possible_next_positions(&map, pos.clone()),
-            |pos| pos_dist(pos, &end_pos),
+            |pos| pos.manhattan_distance(&end_pos),
             |pos| *pos == end_pos,
         )
         .expect("no solution found");
@@ -79,13 +72,13 @@ impl AocDay18 {
         let mut high = self.bytes.len();
         while low != high {
             let i = (low + high + 1) / 2;
-            let mut map = Array2::default((MAP_SIZE + 1, MAP_SIZE + 1));
+            let mut map = Grid::from_elem(Pos::new(MAP_SIZE + 1, MAP_SIZE + 1), false);
             self.bytes.iter().take(i).for_each(|pos| map[pos] = true);
 
             if astar(
                 &start_pos,
                 |pos| get_possible_next_positions(&map, pos.clone()),
-                |pos| pos_dist(pos, &end_pos),
+                |pos| pos.manhattan_distance(&end_pos),
                 |pos| *pos == end_pos,
             )
             .is_none()
@@ -1,11 +1,5 @@
-use aoc_common::{
-    navigation::{Direction, Vec2D, VecRadius, VecSum},
-    parsing::try_parse_2d_array,
-    AocDay, DayError,
-};
+use aoc_common::{grid::Grid, navigation::Vec2D, search::DistanceMaps, AocDay, DayError};
 use aoc_common_macros::TryFromChar;
-use ndarray::Array2;
-use pathfinding::prelude::dijkstra;
 
 type Pos = Vec2D<usize>;
 
@@ -21,71 +15,28 @@ enum Tile {
     End,
 }
 
-fn find_adjacent_pos(map: &Array2<Tile>, pos: Pos) -> impl Iterator<Item = (Pos, usize)> + use<'_> {
-    Direction::get_all_orthogonal().flat_map(move |dir| {
-        pos.vec_sum(&Vec2D::<isize>::from(dir))
-            .filter(|new_pos| map[new_pos] != Tile::Wall)
-            .map(|pos| (pos, 1))
-    })
-}
-
-fn get_cheats<'a, 'b, const MAX_JUMP: isize>(
-    dist_map: &'a Array2<Option<usize>>,
-    path: &'b [Pos],
-) -> impl Iterator<Item = usize> + use<'a, 'b, MAX_JUMP> {
-    path.iter()
-        .flat_map(|p| dist_map[p].map(|dist| (p, dist)))
-        .flat_map(|(pos, dist)| {
-            pos.all_points_manhattan_dist(MAX_JUMP)
-                .flat_map(|(p, delta)| {
-                    p.bind_to_map(&Pos::new(dist_map.ncols(), dist_map.nrows()))
-                        .map(|d| (d, delta))
-                })
-                .flat_map(|(p, delta)| dist_map[&p].map(|d| (d, delta)))
-                .filter(move |(d, _)| dist < *d)
-                .map(move |(d, delta)| dist.abs_diff(d).saturating_sub(delta as usize))
-                .filter(|savings| *savings > 0)
-        })
-}
-
 pub struct AocDay20 {
-    dist_map: Array2<Option<usize>>,
-    path: Vec<Pos>,
+    maps: DistanceMaps,
 }
 
 impl AocDay<usize, usize> for AocDay20 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let map: Array2<Tile> = try_parse_2d_array(lines)?;
-        let start_pos = map
-            .indexed_iter()
-            .find(|(_, pos)| **pos == Tile::Start)
-            .map(|((y, x), _)| Pos::new(x, y))
+        let map: Grid<Tile> = Grid::parse(lines)?;
+        let start_pos: Pos = map
+            .find(|tile| *tile == Tile::Start)
             .ok_or(DayError::GenericParseErr("can't find starting pos in map"))?;
+        let end_pos = map
+            .find(|tile| *tile == Tile::End)
+            .ok_or(DayError::GenericParseErr("can't find end pos in map"))?;
 
-        let (path, _) = dijkstra(
-            &start_pos,
-            |pos| find_adjacent_pos(&map, pos.clone()),
-            |pos| map[pos] == Tile::End,
-        )
-        .expect("solution not found");
-
-        let mut dist_map = Array2::default(map.raw_dim());
-
-        path.iter()
-            .enumerate()
-            .for_each(|(dist, pos)| dist_map[pos] = Some(dist));
-
-        Ok(AocDay20 { dist_map, path })
+        let maps = DistanceMaps::new(&map, &start_pos, &end_pos, |tile| *tile != Tile::Wall);
+        Ok(AocDay20 { maps })
     }
     fn part1(&self) -> usize {
-        get_cheats::<2>(&self.dist_map, &self.path)
-            .filter(|savings| *savings >= 100)
-            .count()
+        self.maps.count_shortcuts(2, 100)
     }
     fn part2(&self) -> usize {
-        get_cheats::<20>(&self.dist_map, &self.path)
-            .filter(|savings| *savings >= 100)
-            .count()
+        self.maps.count_shortcuts(20, 100)
     }
 }
 
@@ -95,6 +46,14 @@ mod day20tests {
 
     use super::*;
 
+    /// Steps saved by every cheat of at most `max_jump` picoseconds.
+    fn get_cheats(day: &AocDay20, max_jump: usize) -> impl Iterator<Item = usize> {
+        day.maps
+            .shortcuts(max_jump, 1)
+            .into_iter()
+            .map(|cheat| cheat.saving)
+    }
+
     const INPUT: &[&str] = &[
         "###############",
         "#...#...#.....#",
@@ -129,9 +88,7 @@ mod day20tests {
         expected.extend([40; 1]);
         expected.extend([64; 1]);
 
-        let result = get_cheats::<2>(&day.dist_map, &day.path)
-            .sorted()
-            .collect_vec();
+        let result = get_cheats(&day, 2).sorted().collect_vec();
         assert_eq!(result, expected);
         Ok(())
     }
@@ -155,7 +112,7 @@ mod day20tests {
         expected.extend([74; 4]);
         expected.extend([76; 3]);
 
-        let result = get_cheats::<20>(&day.dist_map, &day.path)
+        let result = get_cheats(&day, 20)
             .filter(|savings| *savings >= 50)
             .sorted()
             .collect_vec();
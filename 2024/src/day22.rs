@@ -1,6 +1,6 @@
 use std::{iter, str::FromStr};
 
-use aoc_common::{AocDay, DayError};
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 use itertools::Itertools;
 
 const MOD: u64 = 16777216;
@@ -39,9 +39,7 @@ pub struct AocDay22 {
 
 impl AocDay<u64, u16> for AocDay22 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let numbers = lines
-            .map(|l| l.parse())
-            .process_results(|it| it.collect_vec())?;
+        let numbers = parse_lines(lines)?;
 
         Ok(AocDay22 { numbers })
     }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, str::FromStr};
 
-use aoc_common::{AocDay, DayError};
+use aoc_common::{parsing::numbered_lines, AocDay, DayError, ErrorContext};
 use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -156,26 +156,33 @@ pub struct AocDay24 {
 }
 
 impl AocDay<usize, String> for AocDay24 {
-    fn preprocessing(mut lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+    fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+        let mut lines = numbered_lines(lines);
         let start_values = lines
             .by_ref()
-            .take_while(|line| !line.is_empty())
+            .take_while(|line| !line.text.is_empty())
             .map(|line| {
-                let (name, value) = line
-                    .split_once(": ")
-                    .ok_or(DayError::GenericParseErr("can't split on start value"))?;
-
-                Ok::<_, DayError>((name.to_string(), value.parse::<u8>()? != 0))
+                line.parse_with(|line| {
+                    let (name, value) = line
+                        .split_once(": ")
+                        .ok_or(DayError::GenericParseErr("can't split on start value"))?;
+
+                    Ok::<_, DayError>((name.to_string(), value.parse::<u8>()? != 0))
+                })
+                .context("while parsing start value in section 1")
             })
             .process_results(|it| it.collect())?;
 
         let gates = lines
             .map(|line| {
-                let (gate, output) = line
-                    .split_once(" -> ")
-                    .ok_or(DayError::GenericParseErr("can't split on gate output"))?;
-
-                Ok::<_, DayError>((output.to_string(), gate.parse()?))
+                line.parse_with(|line| {
+                    let (gate, output) = line
+                        .split_once(" -> ")
+                        .ok_or(DayError::GenericParseErr("can't split on gate output"))?;
+
+                    Ok::<_, DayError>((output.to_string(), gate.parse()?))
+                })
+                .context("while parsing gate in section 2")
             })
             .process_results(|it| it.collect())?;
 
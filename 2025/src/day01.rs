@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use aoc_common::{AocDay, DayError};
-use itertools::Itertools;
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 
 struct Dial {
     max: i32,
@@ -60,9 +59,7 @@ pub struct AocDay01 {
 
 impl AocDay<usize, i32> for AocDay01 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let rotations = lines
-            .map(|l| l.parse())
-            .process_results(|it| it.collect_vec())?;
+        let rotations = parse_lines(lines)?;
 
         Ok(AocDay01 { rotations })
     }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_common::{AocDay, DayError};
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 use itertools::Itertools;
 
 type Battery = u8;
@@ -65,9 +65,7 @@ pub struct AocDay03 {
 
 impl AocDay<u64, u64> for AocDay03 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let banks = lines
-            .map(|l| l.parse())
-            .process_results(|it| it.collect_vec())?;
+        let banks = parse_lines(lines)?;
 
         Ok(AocDay03 { banks })
     }
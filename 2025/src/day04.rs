@@ -1,13 +1,10 @@
 use aoc_common::{
-    navigation::{Direction, Vec2D, VecSum},
-    parsing::try_parse_2d_array,
+    automaton::{Automaton, Neighbours},
+    grid::Grid,
+    navigation::DirectionSet,
     AocDay, DayError,
 };
 use aoc_common_macros::TryFromChar;
-use itertools::Itertools;
-use ndarray::Array2;
-
-type Pos = Vec2D<usize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromChar)]
 enum Tile {
@@ -17,67 +14,40 @@ enum Tile {
     PaperRoll,
 }
 
+/// Paper rolls with fewer than four paper rolls around them can be removed.
+fn remove_accessible(tile: &Tile, neighbours: &Neighbours<'_, Tile>) -> Tile {
+    if *tile == Tile::PaperRoll && neighbours.count(|&tile| tile == Tile::PaperRoll) < 4 {
+        Tile::Air
+    } else {
+        *tile
+    }
+}
+
+fn count_rolls(map: &Grid<Tile>) -> usize {
+    map.iter()
+        .filter(|(_, &tile)| tile == Tile::PaperRoll)
+        .count()
+}
+
 pub struct AocDay04 {
-    map: Array2<Tile>,
+    map: Grid<Tile>,
 }
 
 impl AocDay<usize, usize> for AocDay04 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let map = try_parse_2d_array(lines)?;
+        let map = Grid::parse(lines)?;
 
         Ok(AocDay04 { map })
     }
     fn part1(&self) -> usize {
-        let limits = Pos::new(self.map.ncols(), self.map.nrows());
-        self.map
-            .indexed_iter()
-            .filter(|(_, &tile)| tile == Tile::PaperRoll)
-            .map(|((y, x), _)| Pos::new(x, y))
-            .filter(|pos| {
-                Direction::get_all()
-                    .filter_map(|dir| {
-                        let adj_pos = pos
-                            .vec_sum(&Vec2D::<isize>::from(dir))?
-                            .bind_to_map(&limits)?;
-                        Some(self.map[(adj_pos.y, adj_pos.x)])
-                    })
-                    .filter(|&tile| tile == Tile::PaperRoll)
-                    .count()
-                    < 4
-            })
-            .count()
+        let mut automaton = Automaton::new(self.map.clone());
+        automaton.update(DirectionSet::all(), remove_accessible);
+        count_rolls(&self.map) - count_rolls(automaton.cells())
     }
     fn part2(&self) -> usize {
-        let limits = Pos::new(self.map.ncols(), self.map.nrows());
-        let mut map = self.map.clone();
-        let mut count = 0;
-        loop {
-            let changed = map
-                .indexed_iter()
-                .filter(|(_, &tile)| tile == Tile::PaperRoll)
-                .map(|((y, x), _)| Pos::new(x, y))
-                .filter(|pos| {
-                    Direction::get_all()
-                        .filter_map(|dir| {
-                            let adj_pos = pos
-                                .vec_sum(&Vec2D::<isize>::from(dir))?
-                                .bind_to_map(&limits)?;
-                            Some(map[(adj_pos.y, adj_pos.x)])
-                        })
-                        .filter(|&tile| tile == Tile::PaperRoll)
-                        .count()
-                        < 4
-                })
-                .collect_vec();
-            changed.iter().for_each(|pos| {
-                map[(pos.y, pos.x)] = Tile::Air;
-            });
-            if changed.is_empty() {
-                break;
-            }
-            count += changed.len();
-        }
-        count
+        let mut automaton = Automaton::new(self.map.clone());
+        automaton.run_until_stable(DirectionSet::all(), remove_accessible);
+        count_rolls(&self.map) - count_rolls(automaton.cells())
     }
 }
 
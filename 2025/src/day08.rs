@@ -1,46 +1,8 @@
-use std::str::FromStr;
-
-use aoc_common::{AocDay, DayError};
+use aoc_common::{navigation::Vec3D, parsing::parse_lines, AocDay, DayError};
 use disjoint::DisjointSet;
 use itertools::Itertools;
 
-#[derive(Debug, Clone)]
-struct Pos {
-    x: u64,
-    y: u64,
-    z: u64,
-}
-
-impl FromStr for Pos {
-    type Err = DayError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(',');
-        let x = split
-            .next()
-            .ok_or(DayError::GenericParseErr("missing coordinate x"))?;
-        let y = split
-            .next()
-            .ok_or(DayError::GenericParseErr("missing coordinate y"))?;
-        let z = split
-            .next()
-            .ok_or(DayError::GenericParseErr("missing coordinate z"))?;
-        Ok(Pos {
-            x: x.parse()?,
-            y: y.parse()?,
-            z: z.parse()?,
-        })
-    }
-}
-
-impl Pos {
-    fn distance_to(&self, other: &Pos) -> u64 {
-        // no need to sqrt since we are just comparing
-        self.x.abs_diff(other.x).pow(2)
-            + self.y.abs_diff(other.y).pow(2)
-            + self.z.abs_diff(other.z).pow(2)
-    }
-}
+type Pos = Vec3D<u64>;
 
 // edge in "graph"
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -54,7 +16,8 @@ fn calculate_edges(positions: &[Pos]) -> Vec<Edge> {
     (0..positions.len())
         .tuple_combinations()
         .map(|(pos1, pos2)| Edge {
-            weight: positions[pos1].distance_to(&positions[pos2]),
+            // no need to sqrt since we are just comparing
+            weight: positions[pos1].squared_distance(&positions[pos2]),
             from: pos1,
             to: pos2,
         })
@@ -69,9 +32,7 @@ pub struct AocDay08 {
 
 impl AocDay<usize, u64> for AocDay08 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let junction_boxes = lines
-            .map(|l| l.parse())
-            .process_results(|it| it.collect_vec())?;
+        let junction_boxes = parse_lines(lines)?;
 
         let edges = calculate_edges(&junction_boxes);
         Ok(AocDay08 {
@@ -1,6 +1,6 @@
 use std::{ops::Add, str::FromStr};
 
-use aoc_common::{AocDay, DayError};
+use aoc_common::{parsing::parse_lines, AocDay, DayError};
 use itertools::Itertools;
 use z3::{ast::Int, Optimize, SatResult};
 
@@ -142,9 +142,7 @@ pub struct AocDay10 {
 
 impl AocDay<u64, u64> for AocDay10 {
     fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
-        let machines = lines
-            .map(|line| line.parse())
-            .process_results(|it| it.collect_vec())?;
+        let machines = parse_lines(lines)?;
 
         Ok(AocDay10 { machines })
     }
@@ -0,0 +1,57 @@
+#![feature(array_try_map)]
+use aoc_common::bootstrap::Registry;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+// mod day13;
+// mod day14;
+// mod day15;
+// mod day16;
+// mod day17;
+// mod day18;
+// mod day19;
+// mod day20;
+// mod day21;
+// mod day22;
+// mod day23;
+// mod day24;
+// mod day25;
+
+pub fn register(registry: &mut Registry) {
+    let mut year = registry.year(2025, env!("CARGO_MANIFEST_DIR"));
+    year.day::<day01::AocDay01, _, _>(1);
+    year.day::<day02::AocDay02, _, _>(2);
+    year.day::<day03::AocDay03, _, _>(3);
+    year.day::<day04::AocDay04, _, _>(4);
+    year.day::<day05::AocDay05, _, _>(5);
+    year.day::<day06::AocDay06, _, _>(6);
+    year.day::<day07::AocDay07, _, _>(7);
+    year.day::<day08::AocDay08, _, _>(8);
+    year.day::<day09::AocDay09, _, _>(9);
+    year.day::<day10::AocDay10, _, _>(10);
+    year.day::<day11::AocDay11, _, _>(11);
+    year.day::<day12::AocDay12, _, _>(12);
+    // year.day::<day13::AocDay13, _, _>(13);
+    // year.day::<day14::AocDay14, _, _>(14);
+    // year.day::<day15::AocDay15, _, _>(15);
+    // year.day::<day16::AocDay16, _, _>(16);
+    // year.day::<day17::AocDay17, _, _>(17);
+    // year.day::<day18::AocDay18, _, _>(18);
+    // year.day::<day19::AocDay19, _, _>(19);
+    // year.day::<day20::AocDay20, _, _>(20);
+    // year.day::<day21::AocDay21, _, _>(21);
+    // year.day::<day22::AocDay22, _, _>(22);
+    // year.day::<day23::AocDay23, _, _>(23);
+    // year.day::<day24::AocDay24, _, _>(24);
+    // year.day::<day25::AocDay25, _, _>(25);
+}
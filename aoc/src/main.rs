@@ -0,0 +1,19 @@
+use std::process::ExitCode;
+
+use aoc_common::bootstrap::{run, Registry};
+
+fn main() -> ExitCode {
+    let mut registry = Registry::default();
+    aoc2022::register(&mut registry);
+    aoc2023::register(&mut registry);
+    aoc2024::register(&mut registry);
+    aoc2025::register(&mut registry);
+
+    match run(&registry) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
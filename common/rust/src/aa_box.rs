@@ -0,0 +1,214 @@
+use num_traits::PrimInt;
+
+/// Axis-aligned box of integer points in N dimensions, going from `min`
+/// (included) to `max` (excluded) along every axis. The box is empty if any
+/// of its extents isn't positive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AaBox<T, const N: usize> {
+    pub min: [T; N],
+    pub max: [T; N],
+}
+
+impl<T: PrimInt, const N: usize> AaBox<T, N> {
+    pub fn new(min: [T; N], max: [T; N]) -> Self {
+        AaBox { min, max }
+    }
+
+    /// Box whose opposite corners are `a` and `b`, both included, as they're
+    /// usually given in puzzles.
+    pub fn from_corners(a: [T; N], b: [T; N]) -> Self {
+        AaBox {
+            min: std::array::from_fn(|axis| a[axis].min(b[axis])),
+            max: std::array::from_fn(|axis| a[axis].max(b[axis]) + T::one()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|axis| self.min[axis] >= self.max[axis])
+    }
+
+    /// Number of points of the box along every axis.
+    pub fn extents(&self) -> [T; N] {
+        std::array::from_fn(|axis| {
+            if self.min[axis] < self.max[axis] {
+                self.max[axis] - self.min[axis]
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Number of points in the box.
+    pub fn volume(&self) -> T {
+        self.extents()
+            .into_iter()
+            .fold(T::one(), |volume, extent| volume * extent)
+    }
+
+    pub fn contains_point(&self, point: &[T; N]) -> bool {
+        (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] < self.max[axis])
+    }
+
+    /// Whether every point of `other` is in the box.
+    pub fn contains(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (0..N)
+                .all(|axis| self.min[axis] <= other.min[axis] && other.max[axis] <= self.max[axis])
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = AaBox {
+            min: std::array::from_fn(|axis| self.min[axis].max(other.min[axis])),
+            max: std::array::from_fn(|axis| self.max[axis].min(other.max[axis])),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Disjoint boxes (at most `2 * N` of them) covering the points of the
+    /// box that aren't in `other`.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        // cut off the slabs before and after the overlap, one axis at a time
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut before = rest;
+                before.max[axis] = overlap.min[axis];
+                pieces.push(before);
+            }
+            if overlap.max[axis] < rest.max[axis] {
+                let mut after = rest;
+                after.min[axis] = overlap.max[axis];
+                pieces.push(after);
+            }
+            rest.min[axis] = overlap.min[axis];
+            rest.max[axis] = overlap.max[axis];
+        }
+        pieces
+    }
+
+    /// Every point of the box, with the last axis changing fastest.
+    pub fn points(&self) -> impl Iterator<Item = [T; N]> {
+        let bounds = *self;
+        let first = (!self.is_empty()).then_some(self.min);
+        std::iter::successors(first, move |point| {
+            let mut next = *point;
+            for axis in (0..N).rev() {
+                next[axis] = next[axis] + T::one();
+                if next[axis] < bounds.max[axis] {
+                    return Some(next);
+                }
+                next[axis] = bounds.min[axis];
+            }
+            None
+        })
+    }
+
+    /// Number of points that are on after turning the points of every box on
+    /// or off in turn, counted by splitting the space along the coordinates
+    /// of the boxes.
+    pub fn compressed_volume(steps: &[(Self, bool)]) -> T {
+        fn volume<T: PrimInt, const N: usize>(steps: &[&(AaBox<T, N>, bool)], axis: usize) -> T {
+            if axis == N {
+                return match steps.last() {
+                    Some((_, true)) => T::one(),
+                    _ => T::zero(),
+                };
+            }
+            let mut coordinates: Vec<T> = steps
+                .iter()
+                .flat_map(|(aa_box, _)| [aa_box.min[axis], aa_box.max[axis]])
+                .collect();
+            coordinates.sort();
+            coordinates.dedup();
+
+            coordinates
+                .windows(2)
+                .map(|slab| {
+                    let covering: Vec<_> = steps
+                        .iter()
+                        .filter(|(aa_box, _)| {
+                            aa_box.min[axis] <= slab[0] && slab[1] <= aa_box.max[axis]
+                        })
+                        .copied()
+                        .collect();
+                    if covering.iter().any(|(_, on)| *on) {
+                        (slab[1] - slab[0]) * volume(&covering, axis + 1)
+                    } else {
+                        T::zero()
+                    }
+                })
+                .fold(T::zero(), |total, volume| total + volume)
+        }
+
+        let steps: Vec<_> = steps
+            .iter()
+            .filter(|(aa_box, _)| !aa_box.is_empty())
+            .collect();
+        volume(&steps, 0)
+    }
+
+    /// Number of points in at least one of the boxes.
+    pub fn union_volume(boxes: &[Self]) -> T {
+        let steps: Vec<_> = boxes.iter().map(|aa_box| (*aa_box, true)).collect();
+        Self::compressed_volume(&steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry() {
+        let a = AaBox::from_corners([2, 0, 0], [0, 2, 2]);
+        assert_eq!(a, AaBox::new([0, 0, 0], [3, 3, 3]));
+        assert_eq!(a.volume(), 27);
+        assert!(a.contains_point(&[2, 2, 2]));
+        assert!(!a.contains_point(&[3, 0, 0]));
+
+        let b = AaBox::new([1, 1, 1], [5, 2, 2]);
+        assert_eq!(a.intersection(&b), Some(AaBox::new([1, 1, 1], [3, 2, 2])));
+        assert!(!a.contains(&b));
+        assert!(a.contains(&AaBox::new([1, 1, 1], [3, 2, 2])));
+        assert_eq!(a.intersection(&AaBox::new([3, 0, 0], [4, 1, 1])), None);
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 5);
+        assert_eq!(pieces.iter().map(AaBox::volume).sum::<i32>(), 25);
+        assert!(pieces
+            .iter()
+            .enumerate()
+            .all(|(i, p)| !p.intersects(&b) && pieces[i + 1..].iter().all(|q| !p.intersects(q))));
+        assert_eq!(a.subtract(&a), vec![]);
+
+        assert_eq!(
+            AaBox::new([0, 5], [2, 7]).points().collect::<Vec<_>>(),
+            [[0, 5], [0, 6], [1, 5], [1, 6]]
+        );
+        assert_eq!(AaBox::new([0, 5], [0, 7]).points().count(), 0);
+    }
+
+    #[test]
+    fn volumes() {
+        // reboot steps of the small example of 2021 day 22
+        let steps = [
+            (AaBox::from_corners([10, 10, 10], [12, 12, 12]), true),
+            (AaBox::from_corners([11, 11, 11], [13, 13, 13]), true),
+            (AaBox::from_corners([9, 9, 9], [11, 11, 11]), false),
+            (AaBox::from_corners([10, 10, 10], [10, 10, 10]), true),
+        ];
+        assert_eq!(AaBox::compressed_volume(&steps), 39i64);
+
+        let boxes = [AaBox::new([0, 0], [2, 2]), AaBox::new([1, 1], [3, 3])];
+        assert_eq!(AaBox::union_volume(&boxes), 7);
+    }
+}
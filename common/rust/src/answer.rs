@@ -0,0 +1,213 @@
+use std::fmt::{self, Display};
+
+use ndarray::{Array2, Axis};
+
+/// Answer of a part, as returned by [`AocDay`](crate::AocDay) through
+/// `Into<Answer>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    String(String),
+    /// Letters drawn with pixels, displayed as text when they can be read.
+    Letters(LetterGrid),
+}
+
+impl Answer {
+    /// Kind of answer, either "integer", "string" or "letters".
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::String(_) => "string",
+            Answer::Letters(_) => "letters",
+        }
+    }
+
+    /// Whether the answer is the same as a recorded one. Letters are compared
+    /// by their text, or by their [rendering](LetterGrid::render) if they
+    /// can't be read, without the leading newline of [`Display`].
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Letters(grid) if grid.text().is_none() => grid.render() == expected,
+            _ => self.to_string() == expected,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::String(value) => write!(f, "{}", value),
+            Answer::Letters(grid) => match grid.text() {
+                Some(text) => write!(f, "{}", text),
+                // On their own lines, so that the rows line up after a label.
+                None => write!(f, "\n{}", grid.render()),
+            },
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::String(value.to_string())
+    }
+}
+
+impl From<LetterGrid> for Answer {
+    fn from(value: LetterGrid) -> Self {
+        Answer::Letters(value)
+    }
+}
+
+/// Height of the letters of the Advent of Code font.
+const LETTER_HEIGHT: usize = 6;
+
+/// Letters of the Advent of Code font, without their surrounding blank columns.
+const LETTERS: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Image made of lit and dark pixels, usually spelling capital letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterGrid(Array2<bool>);
+
+impl LetterGrid {
+    pub fn new(pixels: Array2<bool>) -> Self {
+        LetterGrid(pixels)
+    }
+
+    /// Grid where `#` are lit pixels and any other character is dark.
+    /// Short rows are padded with dark pixels.
+    pub fn from_rows<S: AsRef<str>>(rows: impl IntoIterator<Item = S>) -> Self {
+        let rows: Vec<Vec<bool>> = rows
+            .into_iter()
+            .map(|row| row.as_ref().chars().map(|c| c == '#').collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        LetterGrid(Array2::from_shape_fn((rows.len(), width), |(y, x)| {
+            rows[y].get(x).copied().unwrap_or(false)
+        }))
+    }
+
+    pub fn pixels(&self) -> &Array2<bool> {
+        &self.0
+    }
+
+    /// Rows of `#` (lit) and `.` (dark) separated by newlines.
+    pub fn render(&self) -> String {
+        self.0
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Read the letters of the grid, which are separated by blank columns.
+    /// Returns `None` if any of them isn't part of the font.
+    pub fn text(&self) -> Option<String> {
+        if self.0.nrows() != LETTER_HEIGHT {
+            return None;
+        }
+
+        let mut text = String::new();
+        let mut letter: Vec<String> = vec![String::new(); LETTER_HEIGHT];
+        for column in self.0.axis_iter(Axis(1)) {
+            if column.iter().any(|&lit| lit) {
+                for (row, &lit) in letter.iter_mut().zip(column.iter()) {
+                    row.push(if lit { '#' } else { '.' });
+                }
+            } else if !letter[0].is_empty() {
+                text.push(read_letter(&letter)?);
+                letter.iter_mut().for_each(String::clear);
+            }
+        }
+        if !letter[0].is_empty() {
+            text.push(read_letter(&letter)?);
+        }
+
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+fn read_letter(rows: &[String]) -> Option<char> {
+    LETTERS
+        .iter()
+        .find(|(_, shape)| shape.iter().eq(rows.iter()))
+        .map(|(letter, _)| *letter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(42u64).to_string(), "42");
+        assert_eq!(Answer::from(-3i32).kind(), "integer");
+        assert_eq!(Answer::from("4,2,1").to_string(), "4,2,1");
+        assert!(Answer::from(String::from("abc")).matches("abc"));
+    }
+
+    #[test]
+    fn letters() {
+        let grid = LetterGrid::from_rows([
+            "#..#.####.###..",
+            "#..#.#....#..#",
+            "####.###..###.",
+            "#..#.#....#..#",
+            "#..#.#....#..#",
+            "#..#.####.###.",
+        ]);
+        assert_eq!(grid.pixels().dim(), (6, 15));
+        assert_eq!(grid.text().as_deref(), Some("HEB"));
+
+        let answer = Answer::from(grid);
+        assert_eq!(answer.kind(), "letters");
+        assert!(answer.matches("HEB"));
+
+        let unreadable = LetterGrid::from_rows(["##.", ".##"]);
+        assert_eq!(unreadable.text(), None);
+        let answer = Answer::from(unreadable);
+        assert_eq!(answer.to_string(), "\n##.\n.##");
+        assert!(answer.matches("##.\n.##"));
+        assert!(!answer.matches("\n##.\n.##"));
+    }
+}
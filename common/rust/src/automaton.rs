@@ -0,0 +1,440 @@
+use std::mem;
+
+use ndarray::Array2;
+use rustc_hash::FxHashSet;
+
+use crate::grid::Grid;
+use crate::navigation::{Direction, DirectionSet, Topology, Vec2D};
+use crate::sparse_grid::SparseGrid;
+
+type Pos = Vec2D<usize>;
+type SparsePos = Vec2D<isize>;
+
+fn direction_index(direction: Direction) -> usize {
+    direction.to_mask().trailing_zeros() as usize
+}
+
+/// Tiles around the one being updated, as they were before the update.
+pub struct Neighbours<'a, T> {
+    directions: DirectionSet,
+    tiles: Around<'a, T>,
+}
+
+enum Around<'a, T> {
+    /// Tiles of a dense grid, by direction index.
+    Dense([Option<&'a T>; 8]),
+    /// Position in a sparse grid, whose tiles are only looked up when needed.
+    Sparse(&'a SparseGrid<T>, SparsePos),
+}
+
+impl<'a, T> Neighbours<'a, T> {
+    fn dense(directions: DirectionSet, mut lookup: impl FnMut(Direction) -> Option<&'a T>) -> Self {
+        let mut tiles = [None; 8];
+        for direction in directions.iter() {
+            tiles[direction_index(direction)] = lookup(direction);
+        }
+        Neighbours {
+            directions,
+            tiles: Around::Dense(tiles),
+        }
+    }
+
+    fn sparse(directions: DirectionSet, cells: &'a SparseGrid<T>, pos: &SparsePos) -> Self {
+        Neighbours {
+            directions,
+            tiles: Around::Sparse(cells, pos.clone()),
+        }
+    }
+
+    /// Tile next to the updated one in `direction`, if the direction is part
+    /// of the neighbourhood and there's a tile there.
+    pub fn get(&self, direction: Direction) -> Option<&'a T> {
+        if !self.directions.contains(direction) {
+            return None;
+        }
+        match &self.tiles {
+            Around::Dense(tiles) => tiles[direction_index(direction)],
+            Around::Sparse(cells, pos) => cells.get(&(pos + &SparsePos::from(direction))),
+        }
+    }
+
+    /// Every tile of the neighbourhood, along with its direction.
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &'a T)> + '_ {
+        self.directions
+            .iter()
+            .filter_map(|direction| self.get(direction).map(|tile| (direction, tile)))
+    }
+
+    /// Number of tiles of the neighbourhood that match the predicate.
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.iter().filter(|(_, tile)| predicate(tile)).count()
+    }
+}
+
+/// Resize `grid` to `size`, filling it with `fill`, and reusing its memory
+/// unless it's too small.
+fn resize<T: Clone>(grid: &mut Grid<T>, size: Pos, fill: &T) {
+    let old = mem::replace(grid, Grid::from_elem(Pos::new(0, 0), fill.clone()));
+    let (mut tiles, _) = old.into_cells().into_raw_vec_and_offset();
+    tiles.clear();
+    tiles.resize(size.x * size.y, fill.clone());
+    *grid = Grid::new(
+        Array2::from_shape_vec((size.y, size.x), tiles).expect("there is a tile per position"),
+    );
+}
+
+/// What lies beyond the edges of the grid of an [`Automaton`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Edges<T> {
+    /// Nothing, tiles on the edges have fewer neighbours.
+    Bounded,
+    /// The opposite edge of the grid.
+    Toroidal,
+    /// An infinite plane of tiles which are all the same.
+    Infinite(T),
+}
+
+/// Cellular automaton on a grid, where every update computes the new tile of
+/// every position from its old tile and its neighbours, all at once.
+///
+/// A step made of several phases is done by updating with the rule of every
+/// phase in turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Automaton<T> {
+    cells: Grid<T>,
+    /// Tiles of the next generation, kept between updates to reuse the memory.
+    buffer: Grid<T>,
+    edges: Edges<T>,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    /// Automaton where the tiles on the edges of the grid have fewer
+    /// neighbours.
+    pub fn new(cells: Grid<T>) -> Self {
+        Automaton {
+            buffer: cells.clone(),
+            cells,
+            edges: Edges::Bounded,
+        }
+    }
+
+    /// Automaton where the opposite edges of the grid are joined.
+    pub fn toroidal(cells: Grid<T>) -> Self {
+        Automaton {
+            edges: Edges::Toroidal,
+            ..Automaton::new(cells)
+        }
+    }
+
+    /// Automaton on an infinite plane, where all the tiles outside of the
+    /// grid are `background`. As the tiles next to the grid may change, the
+    /// grid grows by one tile on every side at every update.
+    pub fn with_background(cells: Grid<T>, background: T) -> Self {
+        Automaton {
+            edges: Edges::Infinite(background),
+            ..Automaton::new(cells)
+        }
+    }
+
+    pub fn cells(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Grid<T> {
+        self.cells
+    }
+
+    /// Tile of the infinite plane outside of the grid, if there's one.
+    pub fn background(&self) -> Option<&T> {
+        match &self.edges {
+            Edges::Infinite(background) => Some(background),
+            _ => None,
+        }
+    }
+
+    /// Replace every tile (and the background) by the result of `rule`, given
+    /// the old tile and its neighbours in the directions of `neighbourhood`.
+    /// Returns whether any tile changed.
+    pub fn update(
+        &mut self,
+        neighbourhood: DirectionSet,
+        mut rule: impl FnMut(&T, &Neighbours<'_, T>) -> T,
+    ) -> bool {
+        let Automaton {
+            cells,
+            buffer,
+            edges,
+        } = self;
+        if let Edges::Infinite(background) = edges {
+            let size = Pos::new(cells.width() + 2, cells.height() + 2);
+            if buffer.size() != size {
+                resize(buffer, size, background);
+            }
+        }
+        let (bounded, toroidal) = (cells.bounded(), cells.toroidal());
+
+        let mut changed = false;
+        for y in 0..buffer.height() {
+            for x in 0..buffer.width() {
+                let pos = Pos::new(x, y);
+                let (tile, neighbours) = match &*edges {
+                    Edges::Bounded => (
+                        &cells[&pos],
+                        Neighbours::dense(neighbourhood, |direction| {
+                            bounded.step(&pos, direction).map(|(p, _)| &cells[&p])
+                        }),
+                    ),
+                    Edges::Toroidal => (
+                        &cells[&pos],
+                        Neighbours::dense(neighbourhood, |direction| {
+                            toroidal.step(&pos, direction).map(|(p, _)| &cells[&p])
+                        }),
+                    ),
+                    Edges::Infinite(background) => {
+                        // the old grid starts one tile further from the edges
+                        let tile_at = |p: SparsePos| {
+                            let x = usize::try_from(p.x - 1).ok()?;
+                            let y = usize::try_from(p.y - 1).ok()?;
+                            cells.get(&Pos::new(x, y))
+                        };
+                        let signed_pos = SparsePos::new(x as isize, y as isize);
+                        (
+                            tile_at(signed_pos.clone()).unwrap_or(background),
+                            Neighbours::dense(neighbourhood, |direction| {
+                                Some(
+                                    tile_at(&signed_pos + &SparsePos::from(direction))
+                                        .unwrap_or(background),
+                                )
+                            }),
+                        )
+                    }
+                };
+                let new = rule(tile, &neighbours);
+                changed |= new != *tile;
+                buffer[&pos] = new;
+            }
+        }
+        mem::swap(cells, buffer);
+
+        if let Edges::Infinite(background) = edges {
+            let new = rule(
+                background,
+                &Neighbours::dense(neighbourhood, |_| Some(&*background)),
+            );
+            changed |= new != *background;
+            *background = new;
+        }
+        changed
+    }
+
+    /// Update until an update doesn't change anything, and return the number
+    /// of updates, including that last one.
+    pub fn run_until_stable(
+        &mut self,
+        neighbourhood: DirectionSet,
+        mut rule: impl FnMut(&T, &Neighbours<'_, T>) -> T,
+    ) -> usize {
+        let mut updates = 1;
+        while self.update(neighbourhood, &mut rule) {
+            updates += 1;
+        }
+        updates
+    }
+}
+
+/// Cellular automaton on a [`SparseGrid`], where only the tiles that are set
+/// (and the empty tiles next to them, if they can be set) are updated.
+///
+/// Only the tiles that change are written back, so that updates where few of
+/// them do (such as the phases of a step) are cheap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseAutomaton<T> {
+    cells: SparseGrid<T>,
+    /// New tiles of the positions that change, where `None` unsets the tile,
+    /// kept between updates to reuse the memory.
+    changes: Vec<(SparsePos, Option<T>)>,
+    /// Empty positions already given to the rule during an update.
+    seen_empty: FxHashSet<SparsePos>,
+}
+
+impl<T: PartialEq> SparseAutomaton<T> {
+    pub fn new(cells: SparseGrid<T>) -> Self {
+        SparseAutomaton {
+            cells,
+            changes: Vec::new(),
+            seen_empty: FxHashSet::default(),
+        }
+    }
+
+    pub fn cells(&self) -> &SparseGrid<T> {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> SparseGrid<T> {
+        self.cells
+    }
+
+    /// Replace every tile that's set by the result of `rule`, given the old
+    /// tile and its neighbours in the directions of `neighbourhood`, where
+    /// `None` unsets the tile. Empty tiles stay empty.
+    /// Returns whether any tile changed.
+    pub fn update(
+        &mut self,
+        neighbourhood: DirectionSet,
+        mut rule: impl FnMut(&T, &Neighbours<'_, T>) -> Option<T>,
+    ) -> bool {
+        let SparseAutomaton { cells, changes, .. } = self;
+        for (pos, tile) in cells.iter() {
+            let new = rule(tile, &Neighbours::sparse(neighbourhood, cells, pos));
+            if new.as_ref() != Some(tile) {
+                changes.push((pos.clone(), new));
+            }
+        }
+        self.apply_changes()
+    }
+
+    /// Like [`SparseAutomaton::update`], but `rule` is also given the empty
+    /// tiles next to the set ones, as `None`, so that they can be set.
+    pub fn update_with_births(
+        &mut self,
+        neighbourhood: DirectionSet,
+        mut rule: impl FnMut(Option<&T>, &Neighbours<'_, T>) -> Option<T>,
+    ) -> bool {
+        let SparseAutomaton {
+            cells,
+            changes,
+            seen_empty,
+        } = self;
+        for (pos, tile) in cells.iter() {
+            let new = rule(Some(tile), &Neighbours::sparse(neighbourhood, cells, pos));
+            if new.as_ref() != Some(tile) {
+                changes.push((pos.clone(), new));
+            }
+
+            // empty tiles which have this one as a neighbour
+            for direction in neighbourhood.iter() {
+                let empty = pos + &SparsePos::from(direction.opposite());
+                if cells.contains(&empty) || !seen_empty.insert(empty.clone()) {
+                    continue;
+                }
+                if let Some(new) = rule(None, &Neighbours::sparse(neighbourhood, cells, &empty)) {
+                    changes.push((empty, Some(new)));
+                }
+            }
+        }
+        seen_empty.clear();
+        self.apply_changes()
+    }
+
+    /// Write the pending changes into the cells, shrinking the bounds if any
+    /// tile was unset. Returns whether there were any changes.
+    fn apply_changes(&mut self) -> bool {
+        let changed = !self.changes.is_empty();
+        let mut unset = false;
+        for (pos, tile) in self.changes.drain(..) {
+            match tile {
+                Some(tile) => {
+                    self.cells.insert(pos, tile);
+                }
+                None => unset |= self.cells.remove(&pos).is_some(),
+            }
+        }
+        if unset {
+            self.cells.shrink_bounds();
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn life(alive: &bool, neighbours: &Neighbours<'_, bool>) -> bool {
+        matches!(
+            (alive, neighbours.count(|alive| *alive)),
+            (true, 2) | (_, 3)
+        )
+    }
+
+    fn glider() -> Grid<bool> {
+        Grid::from_fn(Pos::new(5, 5), |pos| {
+            [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].contains(&(pos.x, pos.y))
+        })
+    }
+
+    #[test]
+    fn dense() {
+        let mut blinker = Automaton::new(Grid::from_fn(Pos::new(3, 3), |pos| pos.y == 1));
+        assert!(blinker.update(DirectionSet::all(), life));
+        assert_eq!(
+            blinker.cells(),
+            &Grid::from_fn(Pos::new(3, 3), |pos| pos.x == 1)
+        );
+
+        // a glider on a torus comes back after going around
+        let mut torus = Automaton::toroidal(glider());
+        for _ in 0..20 {
+            torus.update(DirectionSet::all(), life);
+        }
+        assert_eq!(torus.cells(), &glider());
+
+        // a glider on an infinite plane keeps going
+        let mut plane = Automaton::with_background(glider(), false);
+        for _ in 0..8 {
+            plane.update(DirectionSet::all(), life);
+        }
+        assert_eq!(plane.cells().size(), Pos::new(21, 21));
+        assert_eq!(plane.cells().iter().filter(|(_, alive)| **alive).count(), 5);
+        assert!(plane.cells()[&Pos::new(12, 12)]);
+
+        // the background can change too
+        let mut flashing =
+            Automaton::with_background(Grid::from_elem(Pos::new(1, 1), false), false);
+        flashing.update(DirectionSet::all(), |alive, _| !alive);
+        assert_eq!(flashing.background(), Some(&true));
+
+        // sand that falls until it can't
+        let mut sand = Automaton::new(Grid::from_fn(Pos::new(1, 4), |pos| pos.y < 2));
+        let fall = |sand: &bool, neighbours: &Neighbours<'_, bool>| {
+            if *sand {
+                neighbours.get(Direction::South).is_none_or(|below| *below)
+            } else {
+                neighbours.get(Direction::North).is_some_and(|above| *above)
+            }
+        };
+        assert_eq!(sand.run_until_stable(DirectionSet::orthogonal(), fall), 4);
+        assert_eq!(
+            sand.cells(),
+            &Grid::from_fn(Pos::new(1, 4), |pos| pos.y >= 2)
+        );
+    }
+
+    #[test]
+    fn sparse() {
+        let cells = SparseGrid::from_array(glider().into_cells(), |alive| *alive);
+        let mut glider = SparseAutomaton::new(cells.clone());
+        for _ in 0..4 {
+            glider.update_with_births(DirectionSet::all(), |alive, neighbours| {
+                life(&alive.is_some(), neighbours).then_some(true)
+            });
+        }
+        assert_eq!(
+            glider.cells().positions().cloned().collect::<HashSet<_>>(),
+            cells
+                .positions()
+                .map(|pos| pos + &SparsePos::new(1, 1))
+                .collect()
+        );
+
+        // without births, the glider dies out
+        let mut lonely = SparseAutomaton::new(cells);
+        assert!(lonely.update(DirectionSet::all(), |_, neighbours| {
+            (neighbours.count(|_| true) >= 2).then_some(true)
+        }));
+        assert_eq!(lonely.cells().len(), 3);
+        assert!(!lonely.update(DirectionSet::all(), |alive, _| Some(*alive)));
+    }
+}
@@ -1,18 +1,52 @@
+use std::collections::BTreeMap;
 use std::env;
-use std::fmt::Display;
-use std::fs::File;
+use std::fmt::{self, Display};
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
+use std::marker::PhantomData;
 use std::num::ParseIntError;
+use std::path::Path;
 
-use crate::{AocDay, DayError};
+use crate::fetch::{fetcher, FetchError};
+use crate::{Answer, AocDay, DayError, ErrorContext};
+
+mod answers;
+mod bench;
+mod input;
+mod json;
+mod scaffold;
+mod verify;
+
+pub use answers::Answers;
+pub use bench::{bench_day, print_day_bench, print_summary, DayBench, PartBench, Timing};
+pub use input::Input;
+pub use json::json_lines;
+pub use scaffold::{extract_example, new_day};
+pub use verify::{verify, verify_day, PartVerification, Verdict};
 
 #[derive(Debug)]
 pub enum Error {
     IoErr(std::io::Error),
-    NoDayProvided,
+    UnknownArgument(String),
+    MissingArgumentValue(&'static str),
+    YearNotNumber(ParseIntError),
     DayNotNumber(ParseIntError),
     PartNotNumber(ParseIntError),
+    InvalidPart(usize),
+    IterationsNotNumber(ParseIntError),
+    ZeroIterations,
+    UnknownFormat(String),
+    NoDaysRegistered,
+    DayNotFound { year: usize, day: usize },
+    YearNotFound(usize),
+    MissingDay,
+    DayAlreadyExists(String),
+    NoExample,
+    InputNotSupported,
+    ReadErr { path: String, err: std::io::Error },
     DayErr(DayError),
+    FetchErr(FetchError),
+    VerificationFailed(usize),
 }
 
 impl From<std::io::Error> for Error {
@@ -27,7 +61,286 @@ impl From<DayError> for Error {
     }
 }
 
-pub fn run_day<T: AocDay<R1, R2>, R1: Display, R2: Display>(puzzle: T, part_number: Option<usize>) {
+impl From<FetchError> for Error {
+    fn from(value: FetchError) -> Self {
+        Error::FetchErr(value)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IoErr(err) => write!(f, "{}", err),
+            Error::UnknownArgument(arg) => write!(f, "unknown argument {}", arg),
+            Error::MissingArgumentValue(name) => write!(f, "missing value for {}", name),
+            Error::YearNotNumber(err) => write!(f, "year is not a number: {}", err),
+            Error::DayNotNumber(err) => write!(f, "day is not a number: {}", err),
+            Error::PartNotNumber(err) => write!(f, "part is not a number: {}", err),
+            Error::InvalidPart(part) => write!(f, "part must be 1 or 2, got {}", part),
+            Error::IterationsNotNumber(err) => {
+                write!(f, "number of iterations is not a number: {}", err)
+            }
+            Error::ZeroIterations => write!(f, "number of iterations must be at least 1"),
+            Error::UnknownFormat(format) => write!(f, "unknown output format {}", format),
+            Error::NoDaysRegistered => write!(f, "no days registered"),
+            Error::DayNotFound { year, day } => write!(f, "day {} of {} not found", day, year),
+            Error::YearNotFound(year) => write!(f, "year {} not found", year),
+            Error::MissingDay => write!(f, "--day is required"),
+            Error::DayAlreadyExists(path) => write!(f, "{} already exists", path),
+            Error::NoExample => write!(f, "no example found in the puzzle description"),
+            Error::InputNotSupported => write!(f, "--input can only be used to run days"),
+            Error::ReadErr { path, err } => write!(f, "could not read {}: {}", path, err),
+            Error::DayErr(err) => write!(f, "{}", err),
+            Error::FetchErr(err) => write!(f, "could not download input: {}", err),
+            Error::VerificationFailed(count) => {
+                write!(f, "{} answers don't match the recorded ones", count)
+            }
+        }
+    }
+}
+
+/// A puzzle that has already been preprocessed, with the types of its
+/// answers erased so that days of any year can be handled uniformly.
+pub trait Solution {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+
+    fn answer(&self, part: usize) -> Answer {
+        if part == 1 {
+            self.part1()
+        } else {
+            self.part2()
+        }
+    }
+}
+
+struct ErasedDay<T, R1, R2>(T, PhantomData<(R1, R2)>);
+
+impl<T: AocDay<R1, R2>, R1: Into<Answer>, R2: Into<Answer>> Solution for ErasedDay<T, R1, R2> {
+    fn part1(&self) -> Answer {
+        self.0.part1().into()
+    }
+    fn part2(&self) -> Answer {
+        self.0.part2().into()
+    }
+}
+
+type Preprocess = fn(Box<dyn Iterator<Item = String>>) -> Result<Box<dyn Solution>, DayError>;
+
+fn preprocess<T, R1, R2>(
+    lines: Box<dyn Iterator<Item = String>>,
+) -> Result<Box<dyn Solution>, DayError>
+where
+    T: AocDay<R1, R2> + 'static,
+    R1: Into<Answer> + 'static,
+    R2: Into<Answer> + 'static,
+{
+    Ok(Box::new(ErasedDay(T::preprocessing(lines)?, PhantomData)))
+}
+
+/// A day registered in a [`Registry`].
+#[derive(Clone, Copy)]
+pub struct DayEntry {
+    pub year: usize,
+    pub day: usize,
+    dir: &'static str,
+    preprocess: Preprocess,
+}
+
+impl DayEntry {
+    pub fn preprocess(
+        &self,
+        lines: impl Iterator<Item = String> + 'static,
+    ) -> Result<Box<dyn Solution>, DayError> {
+        (self.preprocess)(Box::new(lines))
+    }
+
+    /// Read and preprocess the input of the day, with the path of the input
+    /// attached to any error.
+    pub fn preprocess_input(&self) -> Result<Box<dyn Solution>, Error> {
+        self.preprocess_with_context(get_puzzle_input(self)?, &self.input_path())
+    }
+
+    fn preprocess_with_context(
+        &self,
+        lines: impl Iterator<Item = String> + 'static,
+        input_name: &str,
+    ) -> Result<Box<dyn Solution>, Error> {
+        Ok(self
+            .preprocess(lines)
+            .with_context(|| format!("while preprocessing {}", input_name))?)
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("{}/inputs/day{:0>2}.txt", self.dir, self.day)
+    }
+
+    pub fn answers_path(&self) -> String {
+        format!("{}/answers/day{:0>2}.toml", self.dir, self.day)
+    }
+}
+
+/// Collection of every day that can be run, indexed by year and day.
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<(usize, usize), DayEntry>,
+    years: BTreeMap<usize, &'static str>,
+}
+
+impl Registry {
+    /// Start registering the days of a given year, whose inputs and
+    /// answers are stored in the `inputs` and `answers` subdirectories of `dir`.
+    pub fn year(&mut self, year: usize, dir: &'static str) -> YearRegistry<'_> {
+        self.years.insert(year, dir);
+        YearRegistry {
+            registry: self,
+            year,
+            dir,
+        }
+    }
+
+    pub fn get(&self, year: usize, day: usize) -> Option<&DayEntry> {
+        self.days.get(&(year, day))
+    }
+
+    /// All registered days, ordered by year and day.
+    pub fn days(&self) -> impl Iterator<Item = &DayEntry> {
+        self.days.values()
+    }
+
+    pub fn days_of_year(&self, year: usize) -> impl Iterator<Item = &DayEntry> {
+        self.days
+            .range((year, 0)..=(year, usize::MAX))
+            .map(|(_, e)| e)
+    }
+
+    /// Directory of the crate of a year.
+    pub fn year_dir(&self, year: usize) -> Option<&'static str> {
+        self.years.get(&year).copied()
+    }
+
+    pub fn latest_year(&self) -> Option<usize> {
+        self.days.keys().next_back().map(|(year, _)| *year)
+    }
+}
+
+pub struct YearRegistry<'a> {
+    registry: &'a mut Registry,
+    year: usize,
+    dir: &'static str,
+}
+
+impl YearRegistry<'_> {
+    pub fn day<T, R1, R2>(&mut self, day: usize) -> &mut Self
+    where
+        T: AocDay<R1, R2> + 'static,
+        R1: Into<Answer> + 'static,
+        R2: Into<Answer> + 'static,
+    {
+        self.registry.days.insert(
+            (self.year, day),
+            DayEntry {
+                year: self.year,
+                day,
+                dir: self.dir,
+                preprocess: preprocess::<T, R1, R2>,
+            },
+        );
+        self
+    }
+}
+
+/// Command line arguments of the runner.
+/// When the year is omitted, the latest registered year is used, and when
+/// the day is omitted, all days of that year are run.
+/// With `bench`, the days are timed over that many iterations instead of
+/// printing their answers.
+/// `inputs` replace the input files of the days when running a single day,
+/// each of them being run in turn.
+/// `example` is the path of a puzzle description used by `new-day`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub year: Option<usize>,
+    pub day: Option<usize>,
+    pub part: Option<usize>,
+    pub bench: Option<usize>,
+    pub format: OutputFormat,
+    pub inputs: Vec<Input>,
+    pub example: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Print the answers of the selected days.
+    #[default]
+    Run,
+    /// Compare the answers of the selected days with the recorded ones,
+    /// see [`verify`]. Without a year, every registered day is verified.
+    Verify,
+    /// Generate and register the module of a new day, see [`new_day`].
+    NewDay,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable output.
+    #[default]
+    Text,
+    /// One JSON object per line for each part that was run, with its
+    /// answer and timings, see [`json_lines`].
+    Json,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let mut result = Args::default();
+        while let Some(arg) = args.next() {
+            let mut value = |name| args.next().ok_or(Error::MissingArgumentValue(name));
+            match arg.as_str() {
+                "-y" | "--year" => {
+                    let year = value("--year")?.parse().map_err(Error::YearNotNumber)?;
+                    result.year = Some(year);
+                }
+                "-d" | "--day" => {
+                    let day = value("--day")?.parse().map_err(Error::DayNotNumber)?;
+                    result.day = Some(day);
+                }
+                "-p" | "--part" => {
+                    let part = value("--part")?.parse().map_err(Error::PartNotNumber)?;
+                    if part != 1 && part != 2 {
+                        return Err(Error::InvalidPart(part));
+                    }
+                    result.part = Some(part);
+                }
+                "-b" | "--bench" => {
+                    let iterations = value("--bench")?
+                        .parse()
+                        .map_err(Error::IterationsNotNumber)?;
+                    if iterations == 0 {
+                        return Err(Error::ZeroIterations);
+                    }
+                    result.bench = Some(iterations);
+                }
+                "-f" | "--format" => {
+                    result.format = match value("--format")?.as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        format => return Err(Error::UnknownFormat(format.to_string())),
+                    };
+                }
+                "-i" | "--input" => result.inputs.push(Input::from_arg(value("--input")?)),
+                "-e" | "--example" => result.example = Some(value("--example")?),
+                "verify" => result.command = Command::Verify,
+                "new-day" => result.command = Command::NewDay,
+                _ => return Err(Error::UnknownArgument(arg)),
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub fn run_day(puzzle: &dyn Solution, part_number: Option<usize>) {
     if part_number.unwrap_or(1) == 1 {
         println!("Part 1: {}", puzzle.part1());
     }
@@ -36,25 +349,204 @@ pub fn run_day<T: AocDay<R1, R2>, R1: Display, R2: Display>(puzzle: T, part_numb
     }
 }
 
-pub fn get_puzzle_index() -> Result<usize, Error> {
-    env::args()
-        .nth(1)
-        .ok_or(Error::NoDayProvided)?
-        .parse()
-        .map_err(Error::DayNotNumber)
+/// Read the input of a day, downloading it first if it isn't cached yet.
+pub fn get_puzzle_input(entry: &DayEntry) -> Result<impl Iterator<Item = String>, Error> {
+    let path = entry.input_path();
+    if fetcher().fetch_to(entry.year, entry.day, Path::new(&path))? {
+        eprintln!("Downloaded input to {}", path);
+    }
+    Ok(BufReader::new(File::open(path)?)
+        .lines()
+        .map(|r| r.expect("I/O error while reading input")))
 }
 
-pub fn get_part_number() -> Result<Option<usize>, Error> {
-    env::args()
-        .nth(2)
-        .map(|x| x.parse().map_err(Error::PartNotNumber))
-        .transpose()
-        .map(|opt| opt.filter(|&x| x == 1 || x == 2))
+/// Entry point of the runner: parse the command line arguments and run the
+/// selected days from the registry.
+pub fn run(registry: &Registry) -> Result<(), Error> {
+    let args = Args::parse(env::args().skip(1))?;
+
+    let year = args
+        .year
+        .or_else(|| registry.latest_year())
+        .ok_or(Error::NoDaysRegistered)?;
+
+    if args.command == Command::NewDay {
+        let day = args.day.ok_or(Error::MissingDay)?;
+        let dir = registry.year_dir(year).ok_or(Error::YearNotFound(year))?;
+        let description = match &args.example {
+            Some(path) => Some(fs::read_to_string(path).map_err(|err| Error::ReadErr {
+                path: path.clone(),
+                err,
+            })?),
+            None => None,
+        };
+        let path = new_day(dir, day, description.as_deref())?;
+        println!("Generated {} and registered it in {}/src/lib.rs", path, dir);
+        return Ok(());
+    }
+
+    if !args.inputs.is_empty() {
+        if args.command != Command::Run {
+            return Err(Error::InputNotSupported);
+        }
+        if args.day.is_none() {
+            return Err(Error::MissingDay);
+        }
+    }
+    let inputs = if args.inputs.is_empty() {
+        vec![Input::Default]
+    } else {
+        args.inputs.clone()
+    };
+
+    let entries: Vec<&DayEntry> = match args.day {
+        Some(day) => vec![registry
+            .get(year, day)
+            .ok_or(Error::DayNotFound { year, day })?],
+        None if args.command == Command::Verify && args.year.is_none() => registry.days().collect(),
+        None => registry.days_of_year(year).collect(),
+    };
+
+    if args.command == Command::Verify {
+        return verify(&entries, args.part, args.format);
+    }
+
+    if args.format == OutputFormat::Json {
+        for entry in entries {
+            for input in &inputs {
+                let bench = bench_day(entry, input, args.bench.unwrap_or(1), args.part)?;
+                for line in json_lines(&bench) {
+                    println!("{}", line);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(iterations) = args.bench {
+        let mut benches = Vec::new();
+        for entry in entries {
+            for input in &inputs {
+                let bench = bench_day(entry, input, iterations, args.part)?;
+                print_day_bench(&bench);
+                benches.push(bench);
+            }
+        }
+        if benches.len() > 1 {
+            println!();
+            print_summary(&benches);
+        }
+        return Ok(());
+    }
+
+    for entry in entries {
+        if args.day.is_none() {
+            println!("Day {:0>2}", entry.day);
+        }
+        for input in &inputs {
+            if inputs.len() > 1 {
+                println!("Input {}", input.name(entry));
+            }
+            let lines = input.read(entry)?;
+            let puzzle = entry.preprocess_with_context(lines.into_iter(), &input.name(entry))?;
+            run_day(puzzle.as_ref(), args.part);
+        }
+    }
+
+    Ok(())
 }
 
-pub fn get_puzzle_input(puzzle_index: usize) -> Result<impl Iterator<Item = String>, Error> {
-    let input_file = format!("inputs/day{:0>2}.txt", puzzle_index);
-    Ok(BufReader::new(File::open(input_file)?)
-        .lines()
-        .map(|r| r.expect("I/O error while reading input")))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestDay(Vec<String>);
+
+    impl AocDay<usize, String> for TestDay {
+        fn preprocessing(lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+            Ok(TestDay(lines.collect()))
+        }
+        fn part1(&self) -> usize {
+            self.0.len()
+        }
+        fn part2(&self) -> String {
+            self.0.concat()
+        }
+    }
+
+    fn args(args: &[&str]) -> Result<Args, Error> {
+        Args::parse(args.iter().map(|x| String::from(*x)))
+    }
+
+    #[test]
+    fn parse_args() -> Result<(), Error> {
+        assert_eq!(args(&[])?, Args::default());
+        assert_eq!(
+            args(&["--year", "2024", "-d", "5", "--part", "2"])?,
+            Args {
+                command: Command::Run,
+                year: Some(2024),
+                day: Some(5),
+                part: Some(2),
+                bench: None,
+                format: OutputFormat::Text,
+                inputs: Vec::new(),
+                example: None,
+            }
+        );
+        assert_eq!(args(&["-f", "json"])?.format, OutputFormat::Json);
+        assert_eq!(args(&["verify", "-y", "2022"])?.command, Command::Verify);
+        assert_eq!(
+            args(&["-d", "1", "-i", "-", "--input", "other.txt"])?.inputs,
+            [Input::Stdin, Input::Path(String::from("other.txt"))]
+        );
+        let new_day = args(&["new-day", "-d", "3", "--example", "day03.html"])?;
+        assert_eq!(new_day.command, Command::NewDay);
+        assert_eq!(new_day.example.as_deref(), Some("day03.html"));
+        assert!(matches!(
+            args(&["--format", "xml"]),
+            Err(Error::UnknownFormat(_))
+        ));
+        assert_eq!(args(&["--bench", "10"])?.bench, Some(10));
+        assert!(matches!(args(&["-b", "0"]), Err(Error::ZeroIterations)));
+        assert!(matches!(
+            args(&["--day"]),
+            Err(Error::MissingArgumentValue(_))
+        ));
+        assert!(matches!(args(&["--day", "x"]), Err(Error::DayNotNumber(_))));
+        assert!(matches!(args(&["-p", "3"]), Err(Error::InvalidPart(3))));
+        assert!(matches!(args(&["5"]), Err(Error::UnknownArgument(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn registry() -> Result<(), DayError> {
+        let mut registry = Registry::default();
+        registry.year(2023, "2023").day::<TestDay, _, _>(25);
+        registry
+            .year(2024, "2024/inputs")
+            .day::<TestDay, _, _>(2)
+            .day::<TestDay, _, _>(1);
+
+        assert_eq!(registry.latest_year(), Some(2024));
+        assert_eq!(
+            registry
+                .days_of_year(2024)
+                .map(|e| e.day)
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(registry.get(2023, 1).is_none());
+        assert_eq!(registry.year_dir(2024), Some("2024/inputs"));
+
+        let entry = registry.get(2023, 25).unwrap();
+        assert_eq!(entry.input_path(), "2023/inputs/day25.txt");
+        assert_eq!(entry.answers_path(), "2023/answers/day25.toml");
+
+        let puzzle = entry.preprocess(["ab", "c"].into_iter().map(String::from))?;
+        assert_eq!(puzzle.part1(), Answer::Integer(2));
+        assert_eq!(puzzle.answer(2), Answer::String(String::from("abc")));
+        assert_eq!(puzzle.part2().kind(), "string");
+        Ok(())
+    }
 }
@@ -0,0 +1,130 @@
+use std::{fs, io::ErrorKind};
+
+use crate::{parsing::numbered_lines, DayError, ErrorContext};
+
+use super::Error;
+
+/// Expected answers of a day, as recorded in its answers file, for example:
+///
+/// ```toml
+/// part1 = 1234
+/// part2 = "ABCDEF"
+/// ```
+///
+/// Parts without a recorded answer are `None`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Parse the subset of TOML used by answers files: one `partN = value`
+    /// per line, where the value is an integer or a basic string.
+    pub fn parse(content: &str) -> Result<Self, DayError> {
+        let mut answers = Answers::default();
+        for line in numbered_lines(content.lines().map(String::from)) {
+            let text = line.text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let (part, value) = line.parse_with(|text| parse_line(text.trim()))?;
+            if part == 1 {
+                answers.part1 = Some(value);
+            } else {
+                answers.part2 = Some(value);
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Read the answers file at `path`, returning `None` if it doesn't exist.
+    pub fn read(path: &str) -> Result<Option<Self>, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Some(
+                Self::parse(&content).with_context(|| format!("while reading {}", path))?,
+            )),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a `partN = value` line into the part number and its answer.
+fn parse_line(line: &str) -> Result<(usize, String), DayError> {
+    let (key, value) = line
+        .split_once('=')
+        .ok_or(DayError::GenericParseErr("expected key = value"))?;
+    let part = match key.trim() {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(DayError::GenericParseErr("key must be part1 or part2")),
+    };
+    Ok((part, parse_value(value.trim())?))
+}
+
+fn parse_value(value: &str) -> Result<String, DayError> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        let value = value.split('#').next().unwrap_or_default().trim();
+        value.parse::<i128>()?;
+        return Ok(value.to_string());
+    };
+
+    let mut result = String::new();
+    let mut chars = quoted.chars();
+    loop {
+        match chars
+            .next()
+            .ok_or(DayError::GenericParseErr("unterminated string"))?
+        {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('"') => result.push('"'),
+                Some('\\') => result.push('\\'),
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                _ => return Err(DayError::GenericParseErr("unknown escape sequence")),
+            },
+            c => result.push(c),
+        }
+    }
+
+    let rest = chars.as_str().trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(DayError::GenericParseErr("unexpected text after string"));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() -> Result<(), DayError> {
+        assert_eq!(
+            Answers::parse("# day 10\npart1 = 13140\n\npart2 = \"EHZ\\\"\\n\" # letters\n")?,
+            Answers {
+                part1: Some(String::from("13140")),
+                part2: Some(String::from("EHZ\"\n")),
+            }
+        );
+        assert_eq!(Answers::parse("part2 = -5 # comment")?.get(2), Some("-5"));
+        assert_eq!(Answers::parse("")?, Answers::default());
+
+        let err = Answers::parse("part1 = 1\npart3 = 2").unwrap_err();
+        assert_eq!(err.location().map(|l| l.line), Some(2));
+        assert!(Answers::parse("part1 = abc").is_err());
+        assert!(Answers::parse("part1 = \"abc").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use super::{DayEntry, Error, Input};
+
+/// Summary of the durations measured over all iterations of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Timing {
+    /// Returns `None` when there are no samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Some(Timing {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        })
+    }
+}
+
+/// Answer of a part, along with the time it took to compute it.
+#[derive(Debug, Clone)]
+pub struct PartBench {
+    pub answer: String,
+    pub answer_type: &'static str,
+    pub timing: Timing,
+}
+
+/// Timings of the different steps of a day.
+/// The parts that weren't selected to run are `None`.
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub year: usize,
+    pub day: usize,
+    /// Name of the input, unless it's the input file of the day.
+    pub input: Option<String>,
+    pub iterations: usize,
+    pub preprocessing: Timing,
+    pub part1: Option<PartBench>,
+    pub part2: Option<PartBench>,
+}
+
+impl DayBench {
+    /// The parts that were run, along with their number.
+    pub fn parts(&self) -> impl Iterator<Item = (usize, &PartBench)> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, bench)| bench.as_ref().map(|bench| (part, bench)))
+    }
+
+    fn part_median(&self, part: usize) -> Option<Duration> {
+        self.parts()
+            .find(|(number, _)| *number == part)
+            .map(|(_, bench)| bench.timing.median)
+    }
+
+    /// Sum of the medians of all steps.
+    pub fn total(&self) -> Duration {
+        self.preprocessing.median
+            + self
+                .parts()
+                .map(|(_, bench)| bench.timing.median)
+                .sum::<Duration>()
+    }
+}
+
+/// Time the preprocessing and both parts of a day separately, over the given
+/// number of iterations. Reading the input file isn't included in the timings.
+pub fn bench_day(
+    entry: &DayEntry,
+    input: &Input,
+    iterations: usize,
+    part_number: Option<usize>,
+) -> Result<DayBench, Error> {
+    let input_name = input.name(entry);
+    let lines = input.read(entry)?;
+
+    let mut preprocessing = Vec::with_capacity(iterations);
+    let mut samples = [
+        Vec::with_capacity(iterations),
+        Vec::with_capacity(iterations),
+    ];
+    let mut answers = [None, None];
+    for _ in 0..iterations {
+        let lines = lines.clone().into_iter();
+        let start = Instant::now();
+        let puzzle = entry.preprocess_with_context(lines, &input_name)?;
+        preprocessing.push(start.elapsed());
+
+        for part in 1..=2 {
+            if part_number.unwrap_or(part) != part {
+                continue;
+            }
+            let start = Instant::now();
+            let answer = black_box(puzzle.answer(part));
+            samples[part - 1].push(start.elapsed());
+            answers[part - 1] = Some((answer.to_string(), answer.kind()));
+        }
+    }
+
+    let [part1, part2] = [0, 1].map(|i| {
+        let (answer, answer_type) = answers[i].take()?;
+        Some(PartBench {
+            answer,
+            answer_type,
+            timing: Timing::from_samples(&mut samples[i])?,
+        })
+    });
+
+    Ok(DayBench {
+        year: entry.year,
+        day: entry.day,
+        input: (*input != Input::Default).then_some(input_name),
+        iterations,
+        preprocessing: Timing::from_samples(&mut preprocessing).ok_or(Error::ZeroIterations)?,
+        part1,
+        part2,
+    })
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.2?}", duration),
+        None => String::from("-"),
+    }
+}
+
+pub fn print_day_bench(bench: &DayBench) {
+    match &bench.input {
+        Some(input) => println!(
+            "Day {:0>2} on {} ({} iterations)",
+            bench.day, input, bench.iterations
+        ),
+        None => println!("Day {:0>2} ({} iterations)", bench.day, bench.iterations),
+    }
+    println!("{:<15}{:>12}{:>12}{:>12}", "", "min", "median", "mean");
+    for (name, timing) in [
+        ("Preprocessing", Some(bench.preprocessing)),
+        ("Part 1", bench.part1.as_ref().map(|part| part.timing)),
+        ("Part 2", bench.part2.as_ref().map(|part| part.timing)),
+    ] {
+        println!(
+            "{:<15}{:>12}{:>12}{:>12}",
+            name,
+            format_duration(timing.map(|t| t.min)),
+            format_duration(timing.map(|t| t.median)),
+            format_duration(timing.map(|t| t.mean)),
+        );
+    }
+}
+
+/// Print the median timings of several days as a table, with the sum of
+/// every column on the last row.
+pub fn print_summary(benches: &[DayBench]) {
+    println!(
+        "{:<8}{:>15}{:>12}{:>12}{:>12}",
+        "Day", "Preprocessing", "Part 1", "Part 2", "Total"
+    );
+    for bench in benches {
+        println!(
+            "{:<8}{:>15}{:>12}{:>12}{:>12}",
+            format!("{} {:0>2}", bench.year, bench.day),
+            format_duration(Some(bench.preprocessing.median)),
+            format_duration(bench.part_median(1)),
+            format_duration(bench.part_median(2)),
+            format_duration(Some(bench.total())),
+        );
+    }
+
+    let sum = |median: &dyn Fn(&DayBench) -> Option<Duration>| {
+        benches.iter().filter_map(median).reduce(|a, b| a + b)
+    };
+    println!(
+        "{:<8}{:>15}{:>12}{:>12}{:>12}",
+        "Total",
+        format_duration(sum(&|b| Some(b.preprocessing.median))),
+        format_duration(sum(&|b| b.part_median(1))),
+        format_duration(sum(&|b| b.part_median(2))),
+        format_duration(Some(benches.iter().map(DayBench::total).sum())),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect()
+    }
+
+    #[test]
+    fn timing() {
+        assert_eq!(Timing::from_samples(&mut []), None);
+        assert_eq!(
+            Timing::from_samples(&mut ms(&[7, 1, 4])),
+            Some(Timing {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(4),
+                mean: Duration::from_millis(4),
+            })
+        );
+        assert_eq!(
+            Timing::from_samples(&mut ms(&[8, 2, 4, 10])).map(|t| t.median),
+            Some(Duration::from_millis(6))
+        );
+    }
+
+    #[test]
+    fn total() {
+        let timing = |median| Timing {
+            min: Duration::ZERO,
+            median: Duration::from_millis(median),
+            mean: Duration::ZERO,
+        };
+        let bench = DayBench {
+            year: 2024,
+            day: 1,
+            input: None,
+            iterations: 3,
+            preprocessing: timing(1),
+            part1: None,
+            part2: Some(PartBench {
+                answer: String::from("42"),
+                answer_type: "integer",
+                timing: timing(5),
+            }),
+        };
+        assert_eq!(bench.total(), Duration::from_millis(6));
+        assert_eq!(bench.parts().map(|(part, _)| part).collect::<Vec<_>>(), [2]);
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use super::{get_puzzle_input, DayEntry, Error};
+
+/// Where the input of a day is read from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// The input file of the day, downloaded if it's missing, see
+    /// [`get_puzzle_input`].
+    #[default]
+    Default,
+    Stdin,
+    Path(String),
+}
+
+impl Input {
+    /// Parse the value of `--input`, where `-` stands for stdin.
+    pub fn from_arg(arg: String) -> Self {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::Path(arg)
+        }
+    }
+
+    /// Name of the input in messages, such as its path.
+    pub fn name(&self, entry: &DayEntry) -> String {
+        match self {
+            Input::Default => entry.input_path(),
+            Input::Stdin => String::from("stdin"),
+            Input::Path(path) => path.clone(),
+        }
+    }
+
+    /// Read all the lines of the input of a day.
+    pub fn read(&self, entry: &DayEntry) -> Result<Vec<String>, Error> {
+        match self {
+            Input::Default => Ok(get_puzzle_input(entry)?.collect()),
+            Input::Stdin => Ok(io::stdin().lock().lines().collect::<Result<_, _>>()?),
+            Input::Path(path) => {
+                let read_err = |err| Error::ReadErr {
+                    path: path.clone(),
+                    err,
+                };
+                BufReader::new(File::open(path).map_err(read_err)?)
+                    .lines()
+                    .collect::<Result<_, _>>()
+                    .map_err(read_err)
+            }
+        }
+    }
+}
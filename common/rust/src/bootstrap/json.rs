@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use super::{DayBench, Timing};
+
+/// Quote and escape a string so it can be used as a JSON string.
+pub(super) fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => {
+                write!(result, "\\u{:04x}", c as u32).expect("writing to a string can't fail")
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_timing(timing: &Timing) -> String {
+    format!(
+        r#"{{"min_ns":{},"median_ns":{},"mean_ns":{}}}"#,
+        timing.min.as_nanos(),
+        timing.median.as_nanos(),
+        timing.mean.as_nanos()
+    )
+}
+
+/// One JSON object for each part that was run, such as
+/// `{"year":2024,"day":5,"part":1,"answer":"143","answer_type":"integer","iterations":1,
+/// "timings":{"preprocessing":{"min_ns":1,"median_ns":1,"mean_ns":1},"part":{...}}}`
+/// (on a single line), where the answer type is the [`kind`](crate::Answer::kind) of
+/// the answer.
+/// When the day was run on another input than its input file, the name of
+/// that input is added as `"input"` after the part.
+pub fn json_lines(bench: &DayBench) -> Vec<String> {
+    bench
+        .parts()
+        .map(|(part, part_bench)| {
+            format!(
+                concat!(
+                    r#"{{"year":{},"day":{},"part":{}{},"answer":{},"answer_type":{},"#,
+                    r#""iterations":{},"timings":{{"preprocessing":{},"part":{}}}}}"#
+                ),
+                bench.year,
+                bench.day,
+                part,
+                bench
+                    .input
+                    .as_ref()
+                    .map(|input| format!(r#","input":{}"#, json_string(input)))
+                    .unwrap_or_default(),
+                json_string(&part_bench.answer),
+                json_string(part_bench.answer_type),
+                bench.iterations,
+                json_timing(&bench.preprocessing),
+                json_timing(&part_bench.timing),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::bootstrap::PartBench;
+
+    #[test]
+    fn escape() {
+        assert_eq!(json_string("abc"), r#""abc""#);
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn lines() {
+        let timing = |ns| Timing {
+            min: Duration::from_nanos(ns),
+            median: Duration::from_nanos(ns),
+            mean: Duration::from_nanos(ns),
+        };
+        let mut bench = DayBench {
+            year: 2022,
+            day: 10,
+            input: None,
+            iterations: 1,
+            preprocessing: timing(5),
+            part1: None,
+            part2: Some(PartBench {
+                answer: String::from("#.\n.#"),
+                answer_type: "string",
+                timing: timing(7),
+            }),
+        };
+        assert_eq!(
+            json_lines(&bench),
+            [concat!(
+                r##"{"year":2022,"day":10,"part":2,"answer":"#.\n.#","answer_type":"string","##,
+                r#""iterations":1,"timings":{"preprocessing":{"min_ns":5,"median_ns":5,"#,
+                r#""mean_ns":5},"part":{"min_ns":7,"median_ns":7,"mean_ns":7}}}"#
+            )]
+        );
+
+        bench.input = Some(String::from("stdin"));
+        assert!(json_lines(&bench)[0]
+            .starts_with(r#"{"year":2022,"day":10,"part":2,"input":"stdin","answer":"#));
+    }
+}
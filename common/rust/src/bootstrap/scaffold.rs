@@ -0,0 +1,220 @@
+use std::{fs, path::Path};
+
+use super::Error;
+
+/// Generate `src/dayNN.rs` in the crate of a year from its
+/// `src/day00.rs.template`, and register the new day in its `src/lib.rs`.
+/// When given, the first example of the puzzle description is used as the
+/// input of the generated tests.
+/// Returns the path of the generated file.
+pub fn new_day(dir: &str, day: usize, description: Option<&str>) -> Result<String, Error> {
+    let path = format!("{}/src/day{:0>2}.rs", dir, day);
+    if Path::new(&path).exists() {
+        return Err(Error::DayAlreadyExists(path));
+    }
+    let example = match description {
+        Some(description) => Some(extract_example(description).ok_or(Error::NoExample)?),
+        None => None,
+    };
+
+    let template = read(&format!("{}/src/day00.rs.template", dir))?;
+    let lib_path = format!("{}/src/lib.rs", dir);
+    let lib = read(&lib_path)?;
+
+    fs::write(&path, render_template(&template, day, example.as_deref()))?;
+    fs::write(&lib_path, register_day(&lib, day))?;
+    Ok(path)
+}
+
+fn read(path: &str) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|err| Error::ReadErr {
+        path: path.to_string(),
+        err,
+    })
+}
+
+/// Replace the day number of the template, and its empty test input with
+/// the lines of `example`.
+fn render_template(template: &str, day: usize, example: Option<&str>) -> String {
+    let mut result = template.replace("00", &format!("{:0>2}", day));
+    if let Some(example) = example {
+        let mut input = String::from("const INPUT: &[&str] = &[\n");
+        for line in example.trim_end_matches('\n').lines() {
+            input.push_str(&format!("        {:?},\n", line));
+        }
+        input.push_str("    ];");
+        result = result.replace(r#"const INPUT: &[&str] = &[""];"#, &input);
+    }
+    result
+}
+
+/// Add the module of a day and its registration to the `lib.rs` of a year,
+/// uncommenting them if they are already there.
+fn register_day(lib: &str, day: usize) -> String {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let after_uses = lines
+        .iter()
+        .rposition(|line| line.starts_with("use "))
+        .map_or(0, |i| i + 1);
+    enable_line(
+        &mut lines,
+        "",
+        &format!("mod day{:0>2};", day),
+        "mod day",
+        after_uses,
+    );
+
+    let after_year = lines
+        .iter()
+        .position(|line| line.contains("registry.year("))
+        .map_or(lines.len(), |i| i + 1);
+    enable_line(
+        &mut lines,
+        "    ",
+        &format!(
+            "year.day::<day{:0>2}::AocDay{:0>2}, _, _>({});",
+            day, day, day
+        ),
+        "year.day::<day",
+        after_year,
+    );
+
+    lines.join("\n") + "\n"
+}
+
+/// Make sure `line` is present and not commented out. It's inserted in order
+/// among the other (possibly commented) lines starting with `prefix`, or at
+/// `fallback` if there are none.
+fn enable_line(lines: &mut Vec<String>, indent: &str, line: &str, prefix: &str, fallback: usize) {
+    let uncommented = |text: &str| {
+        let text = text.trim();
+        text.strip_prefix("// ").unwrap_or(text).to_string()
+    };
+
+    if let Some(existing) = lines.iter_mut().find(|text| uncommented(text) == line) {
+        *existing = format!("{}{}", indent, line);
+        return;
+    }
+
+    let siblings: Vec<usize> = (0..lines.len())
+        .filter(|&i| uncommented(&lines[i]).starts_with(prefix))
+        .collect();
+    let position = siblings
+        .iter()
+        .find(|&&i| uncommented(&lines[i]).as_str() > line)
+        .copied()
+        .or_else(|| siblings.last().map(|i| i + 1))
+        .unwrap_or(fallback);
+    lines.insert(position, format!("{}{}", indent, line));
+}
+
+/// The first example of a puzzle description, either saved from the website
+/// (the first `<pre><code>` block) or converted to Markdown (the first fenced
+/// code block).
+pub fn extract_example(description: &str) -> Option<String> {
+    if let Some(start) = description.find("<pre><code>") {
+        let block = &description[start + "<pre><code>".len()..];
+        let block = &block[..block.find("</code></pre>")?];
+        return Some(unescape_html(&strip_tags(block)));
+    }
+
+    let block = &description[description.find("```")? + 3..];
+    // Skip the language of the block.
+    let block = &block[block.find('\n')? + 1..];
+    Some(block[..block.find("```")?].to_string())
+}
+
+/// Remove tags such as `<em>` used to highlight parts of the examples.
+fn strip_tags(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
+fn unescape_html(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let html = "<p>For example:</p>\n<pre><code>3   4\n<em>4</em>   3\n&lt;&amp;&gt;\n</code></pre>\n<pre><code>other</code></pre>";
+        assert_eq!(
+            extract_example(html).as_deref(),
+            Some("3   4\n4   3\n<&>\n")
+        );
+        let markdown = "For example:\n\n```text\n..#\n#..\n```\n";
+        assert_eq!(extract_example(markdown).as_deref(), Some("..#\n#..\n"));
+        assert_eq!(extract_example("no example"), None);
+    }
+
+    #[test]
+    fn template() {
+        let template = "pub struct AocDay00;\n    const INPUT: &[&str] = &[\"\"];\n";
+        assert_eq!(
+            render_template(template, 7, Some("a\"b\n\nc\n")),
+            "pub struct AocDay07;\n    const INPUT: &[&str] = &[\n        \"a\\\"b\",\n        \"\",\n        \"c\",\n    ];\n"
+        );
+        assert_eq!(
+            render_template(template, 12, None),
+            template.replace("00", "12")
+        );
+    }
+
+    #[test]
+    fn register() {
+        let lib = concat!(
+            "use aoc_common::bootstrap::Registry;\n\n",
+            "mod day01;\n// mod day02;\n\n",
+            "pub fn register(registry: &mut Registry) {\n",
+            "    let mut year = registry.year(2025, env!(\"CARGO_MANIFEST_DIR\"));\n",
+            "    year.day::<day01::AocDay01, _, _>(1);\n",
+            "    // year.day::<day02::AocDay02, _, _>(2);\n",
+            "}\n"
+        );
+        assert_eq!(
+            register_day(lib, 2),
+            lib.replace("// mod", "mod").replace("// year", "year")
+        );
+        assert_eq!(
+            register_day(lib, 3),
+            lib.replace("// mod day02;\n", "// mod day02;\nmod day03;\n")
+                .replace(
+                    "(2);\n",
+                    "(2);\n    year.day::<day03::AocDay03, _, _>(3);\n"
+                )
+        );
+
+        let empty = concat!(
+            "use aoc_common::bootstrap::Registry;\n\n",
+            "pub fn register(registry: &mut Registry) {\n",
+            "    let mut year = registry.year(2026, env!(\"CARGO_MANIFEST_DIR\"));\n",
+            "}\n"
+        );
+        assert_eq!(
+            register_day(empty, 1),
+            empty
+                .replace("Registry;\n", "Registry;\nmod day01;\n")
+                .replace(
+                    "DIR\"));\n",
+                    "DIR\"));\n    year.day::<day01::AocDay01, _, _>(1);\n"
+                )
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    fmt::{self, Display},
+    path::Path,
+};
+
+use crate::fetch::fetcher;
+
+use super::{answers::Answers, json::json_string, DayEntry, Error, OutputFormat};
+
+/// Result of comparing an answer with the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer was recorded for this part.
+    Missing,
+    /// The day couldn't be run, e.g. because its input failed to parse.
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PartVerification {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Display for PartVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {:0>2} part {}: ", self.year, self.day, self.part)?;
+        match &self.verdict {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => {
+                write!(f, "FAIL, expected {:?} but got {:?}", expected, self.answer)
+            }
+            Verdict::Missing => write!(f, "missing, got {:?}", self.answer),
+            Verdict::Error(message) => write!(f, "FAIL, {}", message),
+        }
+    }
+}
+
+impl PartVerification {
+    fn to_json(&self) -> String {
+        let (status, expected) = match &self.verdict {
+            Verdict::Pass => ("pass", json_string(&self.answer)),
+            Verdict::Fail { expected } => ("fail", json_string(expected)),
+            Verdict::Missing => ("missing", String::from("null")),
+            Verdict::Error(message) => {
+                return format!(
+                    r#"{{"year":{},"day":{},"part":{},"status":"fail","error":{}}}"#,
+                    self.year,
+                    self.day,
+                    self.part,
+                    json_string(message)
+                );
+            }
+        };
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"status":"{}","answer":{},"expected":{}}}"#,
+            self.year,
+            self.day,
+            self.part,
+            status,
+            json_string(&self.answer),
+            expected
+        )
+    }
+}
+
+/// Run the selected parts of a day and compare them with the answers
+/// recorded in its answers file.
+/// Returns `None` when the input of the day is missing and can't be downloaded
+/// because no session is configured.
+/// If the day can't be run, e.g. because its input fails to parse, every
+/// selected part is reported as failed with the error.
+pub fn verify_day(
+    entry: &DayEntry,
+    part_number: Option<usize>,
+) -> Result<Option<Vec<PartVerification>>, Error> {
+    if !Path::new(&entry.input_path()).exists() && !fetcher().has_session() {
+        return Ok(None);
+    }
+    let answers = Answers::read(&entry.answers_path())?.unwrap_or_default();
+    let parts = (1..=2).filter(|part| part_number.unwrap_or(*part) == *part);
+    let puzzle = match entry.preprocess_input() {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            return Ok(Some(
+                parts
+                    .map(|part| PartVerification {
+                        year: entry.year,
+                        day: entry.day,
+                        part,
+                        answer: String::new(),
+                        verdict: Verdict::Error(err.to_string()),
+                    })
+                    .collect(),
+            ))
+        }
+    };
+
+    Ok(Some(
+        parts
+            .map(|part| {
+                let answer = puzzle.answer(part);
+                let verdict = match answers.get(part) {
+                    None => Verdict::Missing,
+                    Some(expected) if answer.matches(expected) => Verdict::Pass,
+                    Some(expected) => Verdict::Fail {
+                        expected: expected.to_string(),
+                    },
+                };
+                PartVerification {
+                    year: entry.year,
+                    day: entry.day,
+                    part,
+                    answer: answer.to_string(),
+                    verdict,
+                }
+            })
+            .collect(),
+    ))
+}
+
+/// Verify the given days, printing the verdict of every part followed by a
+/// summary. Fails if any answer doesn't match the recorded one.
+pub fn verify(
+    entries: &[&DayEntry],
+    part_number: Option<usize>,
+    format: OutputFormat,
+) -> Result<(), Error> {
+    let (mut passed, mut failed, mut missing, mut without_input) = (0, 0, 0, 0);
+    for entry in entries {
+        let Some(verifications) = verify_day(entry, part_number)? else {
+            without_input += 1;
+            match format {
+                OutputFormat::Text => {
+                    println!("{} day {:0>2}: no input", entry.year, entry.day)
+                }
+                OutputFormat::Json => println!(
+                    r#"{{"year":{},"day":{},"status":"no_input"}}"#,
+                    entry.year, entry.day
+                ),
+            }
+            continue;
+        };
+
+        for verification in verifications {
+            match verification.verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } | Verdict::Error(_) => failed += 1,
+                Verdict::Missing => missing += 1,
+            }
+            match format {
+                OutputFormat::Text => println!("{}", verification),
+                OutputFormat::Json => println!("{}", verification.to_json()),
+            }
+        }
+    }
+
+    if format == OutputFormat::Text {
+        println!(
+            "{} passed, {} failed, {} missing, {} days without input",
+            passed, failed, missing, without_input
+        );
+    }
+
+    if failed > 0 {
+        Err(Error::VerificationFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::{bootstrap::Registry, AocDay, DayError};
+
+    struct FailingDay;
+
+    impl AocDay<usize, usize> for FailingDay {
+        fn preprocessing(_lines: impl Iterator<Item = String>) -> Result<Self, DayError> {
+            Err(DayError::GenericParseErr("input is empty"))
+        }
+        fn part1(&self) -> usize {
+            1
+        }
+        fn part2(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn display() {
+        let verification = |verdict| PartVerification {
+            year: 2024,
+            day: 3,
+            part: 2,
+            answer: String::from("42"),
+            verdict,
+        };
+        assert_eq!(
+            verification(Verdict::Pass).to_string(),
+            "2024 day 03 part 2: pass"
+        );
+        assert_eq!(
+            verification(Verdict::Fail {
+                expected: String::from("41")
+            })
+            .to_string(),
+            r#"2024 day 03 part 2: FAIL, expected "41" but got "42""#
+        );
+        assert_eq!(
+            verification(Verdict::Missing).to_json(),
+            r#"{"year":2024,"day":3,"part":2,"status":"missing","answer":"42","expected":null}"#
+        );
+        assert_eq!(
+            verification(Verdict::Error(String::from("input is empty"))).to_string(),
+            "2024 day 03 part 2: FAIL, input is empty"
+        );
+        assert_eq!(
+            verification(Verdict::Error(String::from("input is empty"))).to_json(),
+            r#"{"year":2024,"day":3,"part":2,"status":"fail","error":"input is empty"}"#
+        );
+    }
+
+    #[test]
+    fn preprocessing_error() -> Result<(), Error> {
+        let dir = env::temp_dir().join(format!("aoc-verify-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("inputs"))?;
+        fs::write(dir.join("inputs/day01.txt"), "")?;
+        let dir: &'static str = Box::leak(dir.to_string_lossy().into_owned().into_boxed_str());
+
+        let mut registry = Registry::default();
+        registry.year(2024, dir).day::<FailingDay, _, _>(1);
+        let verifications = verify_day(registry.get(2024, 1).unwrap(), None)?.unwrap();
+        fs::remove_dir_all(dir)?;
+
+        assert_eq!(
+            verifications.iter().map(|v| v.part).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(verifications.iter().all(|v| matches!(
+            &v.verdict,
+            Verdict::Error(message) if message.starts_with("input is empty")
+        )));
+        Ok(())
+    }
+}
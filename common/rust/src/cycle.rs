@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num_traits::PrimInt;
+
+/// Cycle of a sequence of states, where every state only depends on the
+/// previous one, so that the sequence repeats once a state comes back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// Number of steps before the first state of the cycle.
+    pub start: usize,
+    /// Number of steps to go around the cycle once.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Smallest number of steps that leads to the same state as `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Cycle found by remembering every state until one comes back.
+    /// Loops forever if no state ever comes back.
+    pub fn find<S: Hash + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Self {
+        let mut seen = HashMap::new();
+        let mut state = initial;
+        for i in 0.. {
+            let next = step(&state);
+            if let Some(start) = seen.insert(state, i) {
+                return Cycle {
+                    start,
+                    period: i - start,
+                };
+            }
+            state = next;
+        }
+        unreachable!("the loop only ends by finding a cycle")
+    }
+
+    /// Cycle found with Floyd's tortoise and hare, which only keeps two
+    /// states but computes about three times as many steps as
+    /// [`Cycle::find`]. Loops forever if no state ever comes back.
+    pub fn floyd<S: Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Self {
+        let mut tortoise = step(&initial);
+        let mut hare = step(&tortoise);
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            let halfway = step(&hare);
+            hare = step(&halfway);
+        }
+
+        // the hare is now a multiple of the period ahead of the tortoise
+        let mut start = 0;
+        tortoise = initial;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+
+        let mut period = 1;
+        hare = step(&tortoise);
+        while tortoise != hare {
+            hare = step(&hare);
+            period += 1;
+        }
+        Cycle { start, period }
+    }
+
+    /// Cycle found with Brent's algorithm, which only keeps two states and
+    /// usually computes fewer steps than [`Cycle::floyd`]. Loops forever if
+    /// no state ever comes back.
+    pub fn brent<S: Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Self {
+        // find the period by moving the tortoise to the hare at powers of two
+        let mut power = 1;
+        let mut period = 1;
+        let mut tortoise = initial.clone();
+        let mut hare = step(&initial);
+        while tortoise != hare {
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare = step(&hare);
+            period += 1;
+        }
+
+        // start again with the hare one period ahead of the tortoise
+        let mut start = 0;
+        tortoise = initial.clone();
+        hare = initial;
+        for _ in 0..period {
+            hare = step(&hare);
+        }
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+        Cycle { start, period }
+    }
+}
+
+/// Value of a simulation after `n` steps, simulating only until the states
+/// repeat and extrapolating from there.
+///
+/// `step` moves the state forward, and `key` returns the part of the state
+/// that decides how the simulation goes on, so that two states with the same
+/// key are in the same place of a cycle. The value of a state may then only
+/// change by the same amount every time the cycle is gone around, like the
+/// height of a tower that repeats its pattern.
+pub fn fast_forward<S, K: Hash + Eq, V: PrimInt>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+    value: impl Fn(&S) -> V,
+) -> V {
+    let mut seen = HashMap::new();
+    // value of the state after every number of steps
+    let mut values = Vec::new();
+    let mut state = initial;
+    for i in 0..n {
+        values.push(value(&state));
+        if let Some(start) = seen.insert(key(&state), i) {
+            let cycle = Cycle {
+                start,
+                period: i - start,
+            };
+            let laps = V::from((n - start) / cycle.period).expect("lap count must fit in a value");
+            let end = values[cycle.reduce(n)];
+            return if values[i] >= values[start] {
+                end + (values[i] - values[start]) * laps
+            } else {
+                end - (values[start] - values[i]) * laps
+            };
+        }
+        step(&mut state);
+    }
+    value(&state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sequence that goes through 5 states before a cycle of 7 states.
+    fn next(x: &u32) -> u32 {
+        if *x < 5 {
+            x + 1
+        } else {
+            5 + (x - 5 + 1) % 7
+        }
+    }
+
+    #[test]
+    fn detection() {
+        let expected = Cycle {
+            start: 5,
+            period: 7,
+        };
+        assert_eq!(Cycle::find(0, next), expected);
+        assert_eq!(Cycle::floyd(0, next), expected);
+        assert_eq!(Cycle::brent(0, next), expected);
+        assert_eq!(
+            Cycle::brent(5, next),
+            Cycle {
+                start: 0,
+                period: 7
+            }
+        );
+        assert_eq!(
+            Cycle::floyd(3, |x| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+
+        assert_eq!(expected.reduce(3), 3);
+        assert_eq!(expected.reduce(12), 5);
+        assert_eq!(expected.reduce(1_000_000), 5 + (1_000_000 - 5) % 7);
+    }
+
+    #[test]
+    fn extrapolation() {
+        // the state is the position in the sequence, and the value grows by
+        // the state at every step
+        let simulate = |n: usize| {
+            fast_forward(
+                (0, 0u64),
+                n,
+                |(x, total)| {
+                    *x = next(x);
+                    *total += u64::from(*x);
+                },
+                |(x, _)| *x,
+                |(_, total)| *total,
+            )
+        };
+        let naive = |n: usize| {
+            let mut state = (0, 0u64);
+            for _ in 0..n {
+                state.0 = next(&state.0);
+                state.1 += u64::from(state.0);
+            }
+            state.1
+        };
+        for n in [0, 1, 4, 5, 11, 12, 13, 100, 1234] {
+            assert_eq!(simulate(n), naive(n));
+        }
+
+        // a value that only depends on the key repeats with the cycle
+        let repeated = fast_forward(0, 1_000_000_000, |x| *x = next(x), |x| *x, |x| *x as i32);
+        assert_eq!(repeated, 5 + (1_000_000_000 - 5) % 7);
+    }
+}
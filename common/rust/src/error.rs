@@ -0,0 +1,140 @@
+use std::{
+    fmt::{self, Display},
+    iter,
+    num::ParseIntError,
+};
+
+/// Position in the puzzle input at which an error happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLocation {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number (in characters), starting at 1, when it is known.
+    pub column: Option<usize>,
+    /// The line of the input the error happened on.
+    pub snippet: String,
+}
+
+impl Display for InputLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        write!(f, ": {:?}", self.snippet)
+    }
+}
+
+#[derive(Debug)]
+pub enum DayError {
+    NumParseErr(ParseIntError),
+    GenericParseErr(&'static str),
+    TryFromCharErr(&'static str),
+    /// Error that happened at a known location of the input.
+    Located(InputLocation, Box<DayError>),
+    /// Error with a description of what was being done when it happened,
+    /// e.g. "while parsing gate in section 2".
+    Context(String, Box<DayError>),
+}
+
+impl From<ParseIntError> for DayError {
+    fn from(value: ParseIntError) -> Self {
+        Self::NumParseErr(value)
+    }
+}
+
+impl DayError {
+    /// Record where in the input the error happened.
+    /// If a location was already recorded, e.g. by a helper that knew the
+    /// exact column, that one is kept.
+    pub fn at(self, location: InputLocation) -> Self {
+        if self.location().is_some() {
+            self
+        } else {
+            Self::Located(location, Box::new(self))
+        }
+    }
+
+    /// Describe what was being done when the error happened.
+    pub fn context(self, context: impl Into<String>) -> Self {
+        Self::Context(context.into(), Box::new(self))
+    }
+
+    /// The error, followed by all errors it wraps.
+    fn chain(&self) -> impl Iterator<Item = &DayError> {
+        iter::successors(Some(self), |err| match err {
+            Self::Located(_, inner) | Self::Context(_, inner) => Some(inner),
+            _ => None,
+        })
+    }
+
+    /// The underlying error, without location and context.
+    pub fn root(&self) -> &DayError {
+        self.chain()
+            .last()
+            .expect("chain contains at least the error itself")
+    }
+
+    pub fn location(&self) -> Option<&InputLocation> {
+        self.chain().find_map(|err| match err {
+            Self::Located(location, _) => Some(location),
+            _ => None,
+        })
+    }
+
+    /// All contexts attached to the error, from the innermost to the outermost.
+    pub fn contexts(&self) -> Vec<&str> {
+        let mut contexts = self
+            .chain()
+            .filter_map(|err| match err {
+                Self::Context(context, _) => Some(context.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        contexts.reverse();
+        contexts
+    }
+}
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.root() {
+            Self::NumParseErr(err) => write!(f, "invalid number: {}", err)?,
+            Self::GenericParseErr(msg) => write!(f, "{}", msg)?,
+            Self::TryFromCharErr(target) => write!(f, "can't convert character to {}", target)?,
+            Self::Located(..) | Self::Context(..) => unreachable!("root can't be a wrapper"),
+        }
+        if let Some(location) = self.location() {
+            write!(f, "\n  at {}", location)?;
+        }
+        for context in self.contexts() {
+            write!(f, "\n  {}", context)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.root() {
+            Self::NumParseErr(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Attach context to the error of a [`Result`], see [`DayError::context`].
+pub trait ErrorContext<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, DayError>;
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T, DayError>;
+}
+
+impl<T, E: Into<DayError>> ErrorContext<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T, DayError> {
+        self.map_err(|err| err.into().context(context))
+    }
+
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T, DayError> {
+        self.map_err(|err| err.into().context(context()))
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    env,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ureq::Agent;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum time between two requests made by the default fetcher.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "aoc-common input fetcher";
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingSession,
+    Status { url: String, status: u16 },
+    Request { url: String, message: String },
+    IoErr(io::Error),
+}
+
+impl From<io::Error> for FetchError {
+    fn from(value: io::Error) -> Self {
+        FetchError::IoErr(value)
+    }
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => {
+                write!(f, "AOC_SESSION must be defined to download inputs")
+            }
+            FetchError::Status { url, status } => {
+                write!(f, "{} responded with status {}", url, status)
+            }
+            FetchError::Request { url, message } => {
+                write!(f, "request to {} failed: {}", url, message)
+            }
+            FetchError::IoErr(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Downloads puzzle inputs, waiting between consecutive requests so that the
+/// server isn't hit too often.
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+    min_interval: Duration,
+    agent: Agent,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Fetcher {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Fetcher {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            agent,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Fetcher configured by the `AOC_SESSION` (session cookie) and
+    /// `AOC_BASE_URL` (defaults to [`DEFAULT_BASE_URL`]) environment variables.
+    pub fn from_env() -> Self {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = env::var("AOC_SESSION").ok().filter(|s| !s.is_empty());
+        Fetcher::new(base_url, session)
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.session.is_some()
+    }
+
+    pub fn input_url(&self, year: usize, day: usize) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    /// Download the input of a day.
+    pub fn fetch(&self, year: usize, day: usize) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        let url = self.input_url(year, day);
+        self.wait_turn();
+
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", session))
+            .call();
+        match response {
+            Ok(response) => {
+                response
+                    .into_body()
+                    .read_to_string()
+                    .map_err(|err| FetchError::Request {
+                        url,
+                        message: err.to_string(),
+                    })
+            }
+            Err(ureq::Error::StatusCode(status)) => Err(FetchError::Status { url, status }),
+            Err(err) => Err(FetchError::Request {
+                url,
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    /// Download the input of a day into `path`, unless it's already there.
+    /// Returns whether the input was downloaded.
+    /// The input is written to a temporary file next to `path` and then
+    /// renamed, so an interrupted download never leaves a truncated input.
+    pub fn fetch_to(&self, year: usize, day: usize, path: &Path) -> Result<bool, FetchError> {
+        if path.exists() {
+            return Ok(false);
+        }
+        let input = self.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(format!(".{}.part", process::id()));
+        let partial = PathBuf::from(partial);
+        if let Err(err) = fs::write(&partial, input).and_then(|()| fs::rename(&partial, path)) {
+            let _ = fs::remove_file(&partial);
+            return Err(err.into());
+        }
+        Ok(true)
+    }
+
+    /// Sleep until enough time has passed since the previous request.
+    fn wait_turn(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Fetcher shared by the whole process, configured from the environment.
+pub fn fetcher() -> &'static Fetcher {
+    static FETCHER: OnceLock<Fetcher> = OnceLock::new();
+    FETCHER.get_or_init(Fetcher::from_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    use super::*;
+
+    /// Request line and cookie of a request received by the stand-in server.
+    type Received = (String, Option<String>);
+
+    /// Stand-in server answering `responses` in order, returning the requests
+    /// it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Received>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut cookie = None;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end();
+                        if header.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = header.split_once(": ") {
+                            if name.eq_ignore_ascii_case("cookie") {
+                                cookie = Some(value.to_string());
+                            }
+                        }
+                    }
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    (request_line.trim_end().to_string(), cookie)
+                })
+                .collect()
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let (base_url, server) = serve(vec![(200, "1 2\n3 4\n"), (404, "not yet")]);
+        let fetcher = Fetcher::new(base_url + "/", Some(String::from("abc")))
+            .with_min_interval(Duration::from_millis(100));
+        let dir = env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let path = dir.join("inputs/day05.txt");
+
+        let start = Instant::now();
+        assert!(fetcher.fetch_to(2024, 5, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        // Only the input is left behind, without the temporary file.
+        assert_eq!(fs::read_dir(dir.join("inputs")).unwrap().count(), 1);
+        // Already cached, so no request is made.
+        assert!(!fetcher.fetch_to(2024, 5, &path).unwrap());
+        assert!(matches!(
+            fetcher.fetch(2024, 6),
+            Err(FetchError::Status { status: 404, .. })
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                (
+                    String::from("GET /2024/day/5/input HTTP/1.1"),
+                    Some(String::from("session=abc"))
+                ),
+                (
+                    String::from("GET /2024/day/6/input HTTP/1.1"),
+                    Some(String::from("session=abc"))
+                ),
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_session() {
+        let fetcher = Fetcher::new("http://127.0.0.1:1", None);
+        assert!(!fetcher.has_session());
+        assert!(matches!(
+            fetcher.fetch(2024, 1),
+            Err(FetchError::MissingSession)
+        ));
+    }
+}
@@ -0,0 +1,320 @@
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+use crate::grid::Grid;
+use crate::navigation::{Topology, Vec2D};
+use crate::search::dijkstra;
+
+type Pos = Vec2D<usize>;
+
+/// Directed graph with weighted edges, whose nodes are numbered in the order
+/// they're added.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: FxHashMap<N, usize>,
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl<N: Clone + Hash + Eq> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            indices: FxHashMap::default(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Hash + Eq> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Graph of every node that can be reached from the `starts`, where
+    /// `successors` returns the nodes a node leads to along with the weight
+    /// of the edges.
+    pub fn from_successors<I: IntoIterator<Item = (N, usize)>>(
+        starts: impl IntoIterator<Item = N>,
+        mut successors: impl FnMut(&N) -> I,
+    ) -> Self {
+        let mut graph = Graph::new();
+        let mut stack: Vec<usize> = starts
+            .into_iter()
+            .map(|start| graph.add_node(start))
+            .collect();
+        let mut explored = vec![false; graph.len()];
+        while let Some(index) = stack.pop() {
+            if explored[index] {
+                continue;
+            }
+            explored[index] = true;
+            for (next, weight) in successors(&graph.nodes[index]) {
+                let next = graph.add_node(next);
+                if next == explored.len() {
+                    explored.push(false);
+                    stack.push(next);
+                }
+                graph.add_edge(index, next, weight);
+            }
+        }
+        graph
+    }
+
+    /// Index of a node, which is added if it isn't in the graph yet.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        index
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: usize) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Edges leaving a node, as the index of the node they lead to and their
+    /// weight.
+    pub fn edges(&self, index: usize) -> &[(usize, usize)] {
+        &self.edges[index]
+    }
+
+    /// Graph where every corridor is replaced by a single edge, a corridor
+    /// being a chain of nodes that are each next to exactly two others (in
+    /// either direction). Only the junctions, the dead ends and the nodes to
+    /// `keep` are left, and the weight of each edge is the total weight of
+    /// the corridor it replaces. Corridors that can't be gone through in one
+    /// direction don't get an edge in that direction.
+    pub fn contract(&self, keep: impl Fn(&N) -> bool) -> Self {
+        let mut neighbours = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                neighbours[from].push(to);
+                neighbours[to].push(from);
+            }
+        }
+        for neighbours in &mut neighbours {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+
+        let mut contracted = Graph::new();
+        let mut indices = vec![None; self.len()];
+        for (index, node) in self.nodes.iter().enumerate() {
+            if neighbours[index].len() != 2 || keep(node) {
+                indices[index] = Some(contracted.add_node(node.clone()));
+            }
+        }
+
+        for (from, &contracted_from) in indices.iter().enumerate() {
+            let Some(contracted_from) = contracted_from else {
+                continue;
+            };
+            'edges: for &(next, weight) in &self.edges[from] {
+                let (mut previous, mut current, mut length) = (from, next, weight);
+                while indices[current].is_none() {
+                    let ahead =
+                        neighbours[current][usize::from(neighbours[current][0] == previous)];
+                    let Some(&(_, weight)) =
+                        self.edges[current].iter().find(|(to, _)| *to == ahead)
+                    else {
+                        continue 'edges;
+                    };
+                    (previous, current, length) = (current, ahead, length + weight);
+                }
+                contracted.add_edge(contracted_from, indices[current].unwrap(), length);
+            }
+        }
+        contracted
+    }
+
+    /// Total weight of the lightest path from `start` to `end`.
+    pub fn shortest_path(&self, start: usize, end: usize) -> Option<usize> {
+        dijkstra(
+            [start],
+            |&index| self.edges[index].iter().copied(),
+            |&index| index == end,
+        )
+        .map(|(_, weight)| weight)
+    }
+
+    /// Total weight of the heaviest path from `start` to `end` that doesn't
+    /// go through any node twice. This is a depth-first search through every
+    /// such path, so it's only practical on small (contracted) graphs.
+    pub fn longest_path(&self, start: usize, end: usize) -> Option<usize> {
+        // the heaviest edge leaving each node bounds what's left to gain
+        let heaviest: Vec<usize> = self
+            .edges
+            .iter()
+            .map(|edges| edges.iter().map(|(_, weight)| *weight).max().unwrap_or(0))
+            .collect();
+        // if the end can only be reached from a single node, the path has to
+        // go to the end once it gets there
+        let mut into_end = (0..self.len())
+            .filter(|&index| index != end && self.edges[index].iter().any(|(to, _)| *to == end));
+        let funnel = into_end.next().filter(|_| into_end.next().is_none());
+
+        let mut search = LongestPath {
+            graph: self,
+            end,
+            funnel,
+            heaviest: &heaviest,
+            visited: BitSet::new(self.len()),
+            best: None,
+        };
+        search.visit(start, 0, heaviest.iter().sum());
+        search.best
+    }
+}
+
+impl Graph<Pos> {
+    /// Graph of the tiles of a grid that are `passable`, with edges of weight
+    /// 1 between orthogonal neighbours.
+    pub fn from_grid<T>(grid: &Grid<T>, passable: impl Fn(&T) -> bool) -> Self {
+        let mut graph = Graph::new();
+        for (pos, tile) in grid.iter() {
+            if passable(tile) {
+                graph.add_node(pos);
+            }
+        }
+        for from in 0..graph.len() {
+            for to in grid.neighbours4(&graph.nodes[from]) {
+                if let Some(to) = graph.index_of(&to) {
+                    graph.add_edge(from, to, 1);
+                }
+            }
+        }
+        graph
+    }
+}
+
+struct BitSet(Vec<u64>);
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        BitSet(vec![0; len.div_ceil(64)])
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.0[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn toggle(&mut self, i: usize) {
+        self.0[i / 64] ^= 1 << (i % 64);
+    }
+}
+
+/// State of the search of [`Graph::longest_path`].
+struct LongestPath<'a, N> {
+    graph: &'a Graph<N>,
+    end: usize,
+    funnel: Option<usize>,
+    heaviest: &'a [usize],
+    visited: BitSet,
+    best: Option<usize>,
+}
+
+impl<N> LongestPath<'_, N> {
+    /// Visit a node reached after a path of the given `length`, where
+    /// `bound` is the total weight of the heaviest edges leaving the nodes
+    /// that haven't been visited yet.
+    fn visit(&mut self, node: usize, length: usize, bound: usize) {
+        if node == self.end {
+            self.best = Some(self.best.map_or(length, |best| best.max(length)));
+            return;
+        }
+        self.visited.toggle(node);
+        let bound = bound - self.heaviest[node];
+        for &(next, weight) in &self.graph.edges[node] {
+            if self.visited.contains(next)
+                || (self.funnel == Some(node) && next != self.end)
+                || self
+                    .best
+                    .is_some_and(|best| length + weight + bound <= best)
+            {
+                continue;
+            }
+            self.visit(next, length + weight, bound);
+        }
+        self.visited.toggle(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contraction() {
+        // a loop around a pillar, entered off-center so that one way around
+        // is longer than the other
+        let rows = ["###.###", "#.....#", "#.###.#", "#.....#", "#####.#"];
+        let grid = Grid::from_fn(Pos::new(7, 5), |pos| rows[pos.y].as_bytes()[pos.x] != b'#');
+        let graph = Graph::from_grid(&grid, |open| *open);
+        assert_eq!(graph.len(), 14);
+
+        let (start, end) = (Pos::new(3, 0), Pos::new(5, 4));
+        let contracted = graph.contract(|pos| *pos == start || *pos == end);
+        // start, end, and the two junctions where they join the loop
+        assert_eq!(contracted.len(), 4);
+        let junction = contracted.index_of(&Pos::new(3, 1)).unwrap();
+        let (start, end) = (
+            contracted.index_of(&start).unwrap(),
+            contracted.index_of(&end).unwrap(),
+        );
+        assert_eq!(contracted.edges(start), [(junction, 1)]);
+
+        assert_eq!(contracted.shortest_path(start, end), Some(6));
+        assert_eq!(contracted.longest_path(start, end), Some(10));
+        assert_eq!(contracted.longest_path(end, end), Some(0));
+        let (start, end) = (
+            graph.index_of(&Pos::new(3, 0)),
+            graph.index_of(&Pos::new(5, 4)),
+        );
+        assert_eq!(graph.longest_path(start.unwrap(), end.unwrap()), Some(10));
+    }
+
+    #[test]
+    fn one_way() {
+        // a corridor 0 -> 1 -> 2 that can't be gone through backwards
+        let graph = Graph::from_successors([0], |&node: &u32| match node {
+            0 | 1 => vec![(node + 1, 2)],
+            2 => vec![(3, 1), (4, 1), (5, 1)],
+            _ => vec![],
+        });
+        let contracted = graph.contract(|node| *node == 0);
+        let (start, end) = (
+            contracted.index_of(&0).unwrap(),
+            contracted.index_of(&2).unwrap(),
+        );
+        assert_eq!(contracted.index_of(&1), None);
+        assert_eq!(contracted.shortest_path(start, end), Some(4));
+        assert_eq!(contracted.shortest_path(end, start), None);
+        assert_eq!(contracted.longest_path(end, start), None);
+    }
+}
@@ -0,0 +1,280 @@
+use std::ops::{Index, IndexMut};
+
+use ndarray::Array2;
+
+use crate::{
+    navigation::{Bounded, Direction, Topology, Toroidal, Vec2D},
+    parsing::try_parse_2d_array,
+    DayError,
+};
+
+type Pos = Vec2D<usize>;
+
+/// Rectangular map of tiles, indexed by positions whose x is the column and
+/// y the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Array2<T>) -> Self {
+        Grid { cells }
+    }
+
+    pub fn from_elem(size: Pos, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(Array2::from_elem((size.y, size.x), elem))
+    }
+
+    pub fn from_fn(size: Pos, mut f: impl FnMut(Pos) -> T) -> Self {
+        Grid::new(Array2::from_shape_fn((size.y, size.x), |(y, x)| {
+            f(Pos::new(x, y))
+        }))
+    }
+
+    /// Parse a grid where every character is a tile, see [`try_parse_2d_array`].
+    pub fn parse(lines: impl Iterator<Item = String>) -> Result<Self, DayError>
+    where
+        T: TryFrom<char>,
+    {
+        Ok(Grid::new(try_parse_2d_array(lines)?))
+    }
+
+    /// Grid of the same size where every tile is mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.cells.map(f))
+    }
+
+    pub fn cells(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut Array2<T> {
+        &mut self.cells
+    }
+
+    pub fn into_cells(self) -> Array2<T> {
+        self.cells
+    }
+
+    /// Number of columns and rows, which is also the exclusive upper bound of
+    /// the positions.
+    pub fn size(&self) -> Pos {
+        Pos::new(self.width(), self.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        pos.x < self.width() && pos.y < self.height()
+    }
+
+    pub fn get(&self, pos: &Pos) -> Option<&T> {
+        self.cells.get((pos.y, pos.x))
+    }
+
+    pub fn get_mut(&mut self, pos: &Pos) -> Option<&mut T> {
+        self.cells.get_mut((pos.y, pos.x))
+    }
+
+    /// Topology where the edges of the grid can't be crossed, which is the
+    /// one followed by the methods of the grid.
+    pub fn bounded(&self) -> Bounded {
+        Bounded::new(self.size())
+    }
+
+    /// Topology where the opposite edges of the grid are joined.
+    pub fn toroidal(&self) -> Toroidal {
+        Toroidal::new(self.size())
+    }
+
+    /// Positions from `start` (included) to the edge of the grid, going in
+    /// the given direction.
+    pub fn ray(&self, start: Pos, direction: Direction) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(Some(start).filter(|pos| self.contains(pos)), move |pos| {
+            self.step(pos, direction).map(|(pos, _)| pos)
+        })
+    }
+
+    /// Every tile along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .indexed_iter()
+            .map(|((y, x), tile)| (Pos::new(x, y), tile))
+    }
+
+    /// Position of the first tile, row by row, that matches the predicate.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, tile)| predicate(tile))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Positions of all the tiles equal to `value`, row by row.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, tile)| *tile == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells.row(y).into_iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.column(x).into_iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.height()).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width()).map(|x| self.column(x))
+    }
+
+    /// Positions of every diagonal going south east, starting from the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<Pos>> + '_ {
+        let starts = (0..self.height())
+            .rev()
+            .map(|y| Pos::new(0, y))
+            .chain((1..self.width()).map(|x| Pos::new(x, 0)));
+        starts.map(|start| self.ray(start, Direction::SouthEast).collect())
+    }
+
+    /// Positions of every diagonal going south west, starting from the top
+    /// left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<Pos>> + '_ {
+        let starts = (0..self.width()).map(|x| Pos::new(x, 0)).chain(
+            (1..self.height())
+                .filter(|_| self.width() > 0)
+                .map(|y| Pos::new(self.width() - 1, y)),
+        );
+        starts.map(|start| self.ray(start, Direction::SouthWest).collect())
+    }
+}
+
+// Steps, and so neighbours, follow the bounded topology of the grid.
+impl<T> Topology for Grid<T> {
+    fn step(&self, pos: &Pos, direction: Direction) -> Option<(Pos, Direction)> {
+        self.bounded().step(pos, direction)
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(cells: Array2<T>) -> Self {
+        Grid::new(cells)
+    }
+}
+
+impl<T> Index<&Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: &Pos) -> &T {
+        &self.cells[pos]
+    }
+}
+
+impl<T> IndexMut<&Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: &Pos) -> &mut T {
+        &mut self.cells[pos]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(["abc", "def"].into_iter().map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn size_and_index() {
+        let mut grid = grid();
+        assert_eq!(grid.size(), Pos::new(3, 2));
+        assert_eq!(grid[&Pos::new(2, 0)], 'c');
+        assert_eq!(grid.get(&Pos::new(0, 2)), None);
+        grid[&Pos::new(0, 1)] = 'x';
+        assert_eq!(grid.find(|c| *c == 'x'), Some(Pos::new(0, 1)));
+        assert_eq!(
+            grid.positions_of(&'e').collect::<Vec<_>>(),
+            [Pos::new(1, 1)]
+        );
+        assert_eq!(
+            Grid::from_fn(Pos::new(2, 1), |pos| pos.x).cells(),
+            Grid::new(ndarray::arr2(&[[0, 1]])).cells()
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let corner = Pos::new(0, 0);
+        assert_eq!(
+            grid.neighbours4(&corner).collect::<Vec<_>>(),
+            [Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(&corner).count(), 3);
+        assert_eq!(grid.neighbours8(&Pos::new(1, 1)).count(), 5);
+        assert_eq!(grid.step(&corner, Direction::West), None);
+        assert_eq!(
+            grid.step_by(&corner, Direction::East, 2),
+            Some((Pos::new(2, 0), Direction::East))
+        );
+        assert_eq!(grid.step_by(&corner, Direction::East, 3), None);
+        assert_eq!(
+            grid.toroidal().step(&corner, Direction::West),
+            Some((Pos::new(2, 0), Direction::West))
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.rows()
+                .map(|row| row.collect())
+                .collect::<Vec<String>>(),
+            ["abc", "def"]
+        );
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(
+            grid.ray(Pos::new(0, 1), Direction::East)
+                .map(|pos| grid[&pos])
+                .collect::<String>(),
+            "def"
+        );
+
+        let text = |diagonals: Vec<Vec<Pos>>| {
+            diagonals
+                .iter()
+                .map(|diagonal| diagonal.iter().map(|pos| grid[pos]).collect())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(text(grid.diagonals().collect()), ["d", "ae", "bf", "c"]);
+        assert_eq!(
+            text(grid.anti_diagonals().collect()),
+            ["a", "bd", "ce", "f"]
+        );
+
+        let empty = Grid::from_elem(Pos::new(0, 2), ' ');
+        assert!(empty.diagonals().all(|diagonal| diagonal.is_empty()));
+        assert_eq!(empty.anti_diagonals().count(), 0);
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::RangeInclusive;
+
+use num_traits::PrimInt;
+
+/// Set of integers stored as sorted, disjoint intervals. Intervals that
+/// overlap or touch are merged, so the intervals of a set are unique.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Inclusive bounds of the intervals, with at least one value missing
+    /// between two consecutive ones.
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add all the values of a range, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // intervals from `first` to `last` (excluded) overlap or touch the range
+        let first = self
+            .intervals
+            .partition_point(|&(_, e)| e.checked_add(&T::one()).is_some_and(|e| e < start));
+        let last = self
+            .intervals
+            .partition_point(|&(s, _)| end.checked_add(&T::one()).is_none_or(|end| s <= end));
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    /// Remove all the values of a range, splitting the intervals it's inside of.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|&(s, e)| {
+                let before = (s < start).then(|| (s, e.min(start - T::one())));
+                let after = (e > end).then(|| (s.max(end + T::one()), e));
+                [before, after].into_iter().flatten()
+            })
+            .collect();
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|&(_, e)| e < value);
+        self.intervals.get(index).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// Number of values in the set.
+    /// It's counted in 128 bits, so even a set covering every value of `T` fits,
+    /// unless `T` is itself a 128-bit integer.
+    pub fn len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(s, e)| match (s.to_i128(), e.to_i128()) {
+                // the difference may not fit in an i128, but always fits in a u128
+                (Some(s), Some(e)) => e.wrapping_sub(s) as u128 + 1,
+                _ => e.to_u128().unwrap_or_default() - s.to_u128().unwrap_or_default() + 1,
+            })
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Intervals of the set, in increasing order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..=e)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|&(s, _)| s)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|&(_, e)| e)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.ranges());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while let (Some(&(s1, e1)), Some(&(s2, e2))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            if s1.max(s2) <= e1.min(e2) {
+                intervals.push((s1.max(s2), e1.min(e2)));
+            }
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Split the set into the values lower than `point`, and the others.
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        let mut lower = self.clone();
+        lower.remove(point..=T::max_value());
+        let mut upper = self.clone();
+        if point > T::min_value() {
+            upper.remove(T::min_value()..=point - T::one());
+        }
+        (lower, upper)
+    }
+
+    /// Map the values of the set through a piecewise function, where every
+    /// piece moves the values of a range so that its start becomes the given
+    /// value. Values outside of all the pieces are unchanged, and values
+    /// inside several pieces are moved by the first one.
+    pub fn map_piecewise(&self, pieces: impl IntoIterator<Item = (RangeInclusive<T>, T)>) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = IntervalSet::new();
+        for (range, destination) in pieces {
+            let source = *range.start();
+            let piece = IntervalSet::from(range.clone());
+            mapped.extend(
+                unmapped
+                    .intersection(&piece)
+                    .ranges()
+                    .map(|r| *r.start() - source + destination..=*r.end() - source + destination),
+            );
+            unmapped.remove(range);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: PrimInt>(set: &IntervalSet<T>) -> Vec<RangeInclusive<T>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set: IntervalSet<i32> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(ranges(&set), [3..=5, 10..=20]);
+        assert_eq!(set.len(), 14);
+
+        set.insert(6..=8);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(2..=1);
+        assert_eq!(ranges(&set), [3..=8, 10..=20]);
+        set.insert(9..=9);
+        assert_eq!(ranges(&set), [3..=20]);
+
+        set.remove(5..=6);
+        set.remove(20..=30);
+        assert_eq!(ranges(&set), [3..=4, 7..=19]);
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert_eq!((set.min(), set.max()), (Some(3), Some(19)));
+
+        let mut full = IntervalSet::from(0..=u8::MAX);
+        full.insert(3..=4);
+        assert_eq!(ranges(&full), [0..=u8::MAX]);
+        assert_eq!(full.len(), 256);
+        full.remove(0..=0);
+        assert_eq!(full.len(), 255);
+
+        assert_eq!(IntervalSet::from(i64::MIN..=i64::MAX).len(), 1 << 64);
+        assert_eq!(IntervalSet::from(i128::MIN..=-1).len(), 1 << 127);
+        assert_eq!(IntervalSet::from(1..=u128::MAX).len(), u128::MAX);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet<u32> = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet<u32> = [5..=25, 28..=40].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), [0..=40]);
+        assert_eq!(ranges(&a.intersection(&b)), [5..=10, 20..=25, 28..=30]);
+        assert_eq!(ranges(&a.difference(&b)), [0..=4, 26..=27]);
+
+        let (lower, upper) = a.split_at(25);
+        assert_eq!(ranges(&lower), [0..=10, 20..=24]);
+        assert_eq!(ranges(&upper), [25..=30]);
+        assert!(a.split_at(0).0.is_empty());
+    }
+
+    #[test]
+    fn map_piecewise() {
+        // seed-to-soil map of 2023 day 5
+        let seeds: IntervalSet<u32> = [79..=92, 55..=67].into_iter().collect();
+        let soil = seeds.map_piecewise([(98..=99, 50), (50..=97, 52)]);
+        assert_eq!(ranges(&soil), [57..=69, 81..=94]);
+
+        let split = IntervalSet::from(0..=9).map_piecewise([(5..=6, 100), (0..=5, 50)]);
+        assert_eq!(ranges(&split), [7..=9, 50..=54, 100..=101]);
+    }
+}
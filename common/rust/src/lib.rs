@@ -1,24 +1,24 @@
 #![feature(step_trait)]
-use std::{fmt::Display, num::ParseIntError};
-
+pub mod aa_box;
+mod answer;
+pub mod automaton;
 pub mod bootstrap;
+pub mod cycle;
+mod error;
+pub mod fetch;
+pub mod graph;
+pub mod grid;
+pub mod interval_set;
 pub mod navigation;
 pub mod parsing;
+pub mod polygon;
+pub mod search;
+pub mod sparse_grid;
 
-#[derive(Debug)]
-pub enum DayError {
-    NumParseErr(ParseIntError),
-    GenericParseErr(&'static str),
-    TryFromCharErr(&'static str),
-}
-
-impl From<ParseIntError> for DayError {
-    fn from(value: ParseIntError) -> Self {
-        Self::NumParseErr(value)
-    }
-}
+pub use answer::{Answer, LetterGrid};
+pub use error::{DayError, ErrorContext, InputLocation};
 
-pub trait AocDay<R1: Display, R2: Display> {
+pub trait AocDay<R1: Into<Answer>, R2: Into<Answer>> {
     fn preprocessing_tests(lines: &[&str]) -> Result<Self, DayError>
     where
         Self: std::marker::Sized,
@@ -1,8 +1,18 @@
 use std::iter::Step;
-use std::ops::{Index, IndexMut};
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, Index, IndexMut, Mul, Neg, Not, Sub,
+    SubAssign,
+};
+use std::str::FromStr;
 
 use ndarray::Array2;
-use num_traits::{CheckedAdd, CheckedMul, CheckedSub, Euclid, One, Signed, Zero};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, Euclid, One, Signed, ToPrimitive, Zero};
+
+use crate::DayError;
+
+mod cube_net;
+
+pub use cube_net::CubeNet;
 
 /// The sign of an integer.
 enum Sign {
@@ -48,8 +58,29 @@ impl TryIntoUnsigned for u64 {}
 impl TryIntoUnsigned for u128 {}
 impl TryIntoUnsigned for usize {}
 
-/// Direction in 2D space
-#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+/// Add an integer of any sign to `value`, returning None if it overflows.
+fn checked_add_signed<T, D>(value: &T, delta: D) -> Option<T>
+where
+    T: CheckedAdd + CheckedSub,
+    D: TryIntoUnsigned + TryInto<T>,
+{
+    match delta.try_into_unsigned()? {
+        (delta, Sign::Neg) => value.checked_sub(&delta.try_into().ok()?),
+        (delta, Sign::NonNeg) => value.checked_add(&delta.try_into().ok()?),
+    }
+}
+
+/// Absolute difference of two values, which also works for unsigned types.
+fn abs_diff<T: PartialOrd + Sub<Output = T> + Copy>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Direction in 2D space, ordered clockwise from North
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Direction {
     North,
     NorthEast,
@@ -123,6 +154,232 @@ impl Direction {
             Direction::NorthWest => Direction::SouthWest,
         }
     }
+
+    pub fn rotate_cw_45(&self) -> Self {
+        match self {
+            Direction::North => Direction::NorthEast,
+            Direction::NorthEast => Direction::East,
+            Direction::East => Direction::SouthEast,
+            Direction::SouthEast => Direction::South,
+            Direction::South => Direction::SouthWest,
+            Direction::SouthWest => Direction::West,
+            Direction::West => Direction::NorthWest,
+            Direction::NorthWest => Direction::North,
+        }
+    }
+
+    pub fn rotate_ccw_45(&self) -> Self {
+        match self {
+            Direction::North => Direction::NorthWest,
+            Direction::NorthEast => Direction::North,
+            Direction::East => Direction::NorthEast,
+            Direction::SouthEast => Direction::East,
+            Direction::South => Direction::SouthEast,
+            Direction::SouthWest => Direction::South,
+            Direction::West => Direction::SouthWest,
+            Direction::NorthWest => Direction::West,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate_cw_90().rotate_cw_90()
+    }
+
+    /// Whether the direction is North, East, South or West.
+    pub fn is_orthogonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::North | Direction::East | Direction::South | Direction::West
+        )
+    }
+
+    /// Parse an arrow (`^`, `>`, `v` or `<`), as drawn on maps.
+    pub fn from_arrow(c: char) -> Result<Self, DayError> {
+        match c {
+            '^' => Ok(Direction::North),
+            '>' => Ok(Direction::East),
+            'v' => Ok(Direction::South),
+            '<' => Ok(Direction::West),
+            _ => Err(DayError::TryFromCharErr("Direction")),
+        }
+    }
+
+    /// Parse a cardinal point (`N`, `E`, `S` or `W`) or a relative direction
+    /// (`U`, `R`, `D` or `L`).
+    pub fn from_letter(c: char) -> Result<Self, DayError> {
+        match c {
+            'N' | 'U' => Ok(Direction::North),
+            'E' | 'R' => Ok(Direction::East),
+            'S' | 'D' => Ok(Direction::South),
+            'W' | 'L' => Ok(Direction::West),
+            _ => Err(DayError::TryFromCharErr("Direction")),
+        }
+    }
+
+    /// Inverse of [`Direction::from_arrow`], for orthogonal directions.
+    pub fn to_arrow(&self) -> Option<char> {
+        match self {
+            Direction::North => Some('^'),
+            Direction::East => Some('>'),
+            Direction::South => Some('v'),
+            Direction::West => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+/// Set of directions, stored as the union of their [`Direction::to_mask`].
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub fn new() -> Self {
+        DirectionSet(0)
+    }
+
+    pub fn all() -> Self {
+        DirectionSet(u8::MAX)
+    }
+
+    pub fn orthogonal() -> Self {
+        Direction::get_all_orthogonal().collect()
+    }
+
+    pub fn from_mask(mask: u8) -> Self {
+        DirectionSet(mask)
+    }
+
+    pub fn mask(&self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(&self, direction: Direction) -> bool {
+        self.0 & direction.to_mask() != 0
+    }
+
+    /// Add a direction, returning whether it wasn't in the set yet.
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let is_new = !self.contains(direction);
+        self.0 |= direction.to_mask();
+        is_new
+    }
+
+    /// Remove a direction, returning whether it was in the set.
+    pub fn remove(&mut self, direction: Direction) -> bool {
+        let was_present = self.contains(direction);
+        self.0 &= !direction.to_mask();
+        was_present
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(&self, other: Self) -> Self {
+        DirectionSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: Self) -> Self {
+        DirectionSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: Self) -> Self {
+        DirectionSet(self.0 & !other.0)
+    }
+
+    pub fn is_subset(&self, other: Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Directions in the set, clockwise from North.
+    pub fn iter(&self) -> impl Iterator<Item = Direction> {
+        let set = *self;
+        Direction::get_all().filter(move |direction| set.contains(*direction))
+    }
+}
+
+impl From<Direction> for DirectionSet {
+    fn from(value: Direction) -> Self {
+        DirectionSet(value.to_mask())
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        let mut set = DirectionSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Direction> for DirectionSet {
+    fn extend<I: IntoIterator<Item = Direction>>(&mut self, iter: I) {
+        for direction in iter {
+            self.insert(direction);
+        }
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOr<Direction> for DirectionSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Direction) -> Self {
+        self.union(rhs.into())
+    }
+}
+
+impl BitOrAssign for DirectionSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitOrAssign<Direction> for DirectionSet {
+    fn bitor_assign(&mut self, rhs: Direction) {
+        self.insert(rhs);
+    }
+}
+
+impl BitAnd for DirectionSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl BitAndAssign for DirectionSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl Sub for DirectionSet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(rhs)
+    }
+}
+
+impl Not for DirectionSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        DirectionSet(!self.0)
+    }
 }
 
 /// A vector of any type in 2D space
@@ -144,16 +401,10 @@ impl<T: CheckedAdd + CheckedSub, Rhs: TryIntoUnsigned + TryInto<T> + Copy> VecSu
 {
     /// Sum two vectors, returning None if any of the integers overflows.
     fn vec_sum(&self, v: &Vec2D<Rhs>) -> Option<Self> {
-        let x = match v.x.try_into_unsigned()? {
-            (x, Sign::Neg) => self.x.checked_sub(&x.try_into().ok()?)?,
-            (x, Sign::NonNeg) => self.x.checked_add(&x.try_into().ok()?)?,
-        };
-        let y = match v.y.try_into_unsigned()? {
-            (y, Sign::Neg) => self.y.checked_sub(&y.try_into().ok()?)?,
-            (y, Sign::NonNeg) => self.y.checked_add(&y.try_into().ok()?)?,
-        };
-
-        Some(Self { x, y })
+        Some(Self {
+            x: checked_add_signed(&self.x, v.x)?,
+            y: checked_add_signed(&self.y, v.y)?,
+        })
     }
 }
 
@@ -180,6 +431,136 @@ impl<T> Vec2D<T> {
     }
 }
 
+/// Unchecked arithmetic, which panics on overflow in debug builds.
+/// Prefer [`VecSum`] and [`VecScale`] on unsigned grids.
+impl<T: Add<Output = T>> Add for Vec2D<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Clone> Add for &Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn add(self, rhs: Self) -> Vec2D<T> {
+        self.clone() + rhs.clone()
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2D<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2D<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T> + Clone> Sub for &Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn sub(self, rhs: Self) -> Vec2D<T> {
+        self.clone() - rhs.clone()
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2D<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2D<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Clone> Mul<T> for Vec2D<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self {
+            x: self.x * factor.clone(),
+            y: self.y * factor,
+        }
+    }
+}
+
+impl<T: PartialOrd + Add<Output = T> + Sub<Output = T> + Copy> Vec2D<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Number of king moves between two positions.
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        let (x, y) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if x > y {
+            x
+        } else {
+            y
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy> Vec2D<T> {
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the cross product, positive when `other` is clockwise
+    /// from `self` (the y axis points south, as in `From<Direction>`).
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Signed + Copy> Vec2D<T> {
+    /// Vector whose components are the signs of the components of `self`.
+    pub fn signum(&self) -> Self {
+        Self {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    /// Rotate by 90 degrees clockwise, so that North becomes East.
+    pub fn rotate_cw(&self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Rotate by 90 degrees counterclockwise, so that North becomes West.
+    pub fn rotate_ccw(&self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+}
+
 impl<T: PartialOrd + Default> Vec2D<T> {
     pub fn discard_out_of_bounds(self, lower_bound: &Self, upper_bound: &Self) -> Option<Self> {
         Some(self).filter(|vec| {
@@ -204,6 +585,156 @@ impl<T: Euclid> Vec2D<T> {
     }
 }
 
+/// How the positions of a map of a given size are connected, which decides
+/// where a step leads to.
+pub trait Topology {
+    /// Position reached by a step from `pos` toward `direction`, along with
+    /// the direction faced after it (which only changes if the step crosses
+    /// an edge that turns). Returns `None` if the step leaves the map.
+    fn step(&self, pos: &Vec2D<usize>, direction: Direction) -> Option<(Vec2D<usize>, Direction)>;
+
+    /// Position reached by `count` steps from `pos` toward `direction`, along
+    /// with the direction faced after them.
+    fn step_by(
+        &self,
+        pos: &Vec2D<usize>,
+        direction: Direction,
+        count: usize,
+    ) -> Option<(Vec2D<usize>, Direction)> {
+        (0..count).try_fold((pos.clone(), direction), |(pos, direction), _| {
+            self.step(&pos, direction)
+        })
+    }
+
+    /// Positions next to `pos` in the given directions.
+    fn neighbours_in<'a, D: IntoIterator<Item = Direction> + 'a>(
+        &'a self,
+        pos: &Vec2D<usize>,
+        directions: D,
+    ) -> impl Iterator<Item = Vec2D<usize>> + use<'a, Self, D> {
+        let pos = pos.clone();
+        directions
+            .into_iter()
+            .filter_map(move |direction| self.step(&pos, direction).map(|(pos, _)| pos))
+    }
+
+    /// Orthogonal neighbours of `pos`.
+    fn neighbours4(
+        &self,
+        pos: &Vec2D<usize>,
+    ) -> impl Iterator<Item = Vec2D<usize>> + use<'_, Self> {
+        self.neighbours_in(pos, Direction::get_all_orthogonal())
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos`.
+    fn neighbours8(
+        &self,
+        pos: &Vec2D<usize>,
+    ) -> impl Iterator<Item = Vec2D<usize>> + use<'_, Self> {
+        self.neighbours_in(pos, Direction::get_all())
+    }
+}
+
+/// Map whose edges can't be crossed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bounded {
+    pub size: Vec2D<usize>,
+}
+
+impl Bounded {
+    pub fn new(size: Vec2D<usize>) -> Self {
+        Bounded { size }
+    }
+
+    /// Position `delta` away from `pos`, if it's in the map.
+    #[inline]
+    pub fn translate(&self, pos: &Vec2D<usize>, delta: &Vec2D<isize>) -> Option<Vec2D<usize>> {
+        pos.vec_sum(delta)?.bind_to_map(&self.size)
+    }
+}
+
+impl Topology for Bounded {
+    #[inline]
+    fn step(&self, pos: &Vec2D<usize>, direction: Direction) -> Option<(Vec2D<usize>, Direction)> {
+        self.translate(pos, &direction.into())
+            .map(|pos| (pos, direction))
+    }
+}
+
+/// Map whose opposite edges are joined, so that leaving it through an edge
+/// leads back in through the other one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toroidal {
+    pub size: Vec2D<usize>,
+}
+
+impl Toroidal {
+    pub fn new(size: Vec2D<usize>) -> Self {
+        Toroidal { size }
+    }
+
+    /// Position `delta` away from `pos`, wrapping around the edges as many
+    /// times as needed.
+    #[inline]
+    pub fn translate(&self, pos: &Vec2D<usize>, delta: &Vec2D<isize>) -> Vec2D<usize> {
+        // divisions are only needed to wrap more than once, which steps don't
+        let wrap = |pos: usize, delta: isize, size: usize| {
+            let reduce = |n: usize| if n < size { n } else { n % size };
+            let (pos, offset) = (reduce(pos), reduce(delta.unsigned_abs()));
+            if delta >= 0 && offset < size - pos {
+                pos + offset
+            } else if delta >= 0 {
+                offset - (size - pos)
+            } else if offset <= pos {
+                pos - offset
+            } else {
+                pos + (size - offset)
+            }
+        };
+        Vec2D::new(
+            wrap(pos.x, delta.x, self.size.x),
+            wrap(pos.y, delta.y, self.size.y),
+        )
+    }
+}
+
+impl Topology for Toroidal {
+    #[inline]
+    fn step(&self, pos: &Vec2D<usize>, direction: Direction) -> Option<(Vec2D<usize>, Direction)> {
+        Some((self.translate(pos, &direction.into()), direction))
+    }
+}
+
+/// Map whose edges are connected by a custom function, which is given the
+/// position on the edge and the direction leaving the map, and returns where
+/// the step leads to and the direction faced after it, if anywhere.
+#[derive(Debug, Clone)]
+pub struct EdgeMap<F> {
+    pub size: Vec2D<usize>,
+    cross_edge: F,
+}
+
+impl<F> EdgeMap<F>
+where
+    F: Fn(&Vec2D<usize>, Direction) -> Option<(Vec2D<usize>, Direction)>,
+{
+    pub fn new(size: Vec2D<usize>, cross_edge: F) -> Self {
+        EdgeMap { size, cross_edge }
+    }
+}
+
+impl<F> Topology for EdgeMap<F>
+where
+    F: Fn(&Vec2D<usize>, Direction) -> Option<(Vec2D<usize>, Direction)>,
+{
+    fn step(&self, pos: &Vec2D<usize>, direction: Direction) -> Option<(Vec2D<usize>, Direction)> {
+        match Bounded::new(self.size.clone()).step(pos, direction) {
+            Some(step) => Some(step),
+            None => (self.cross_edge)(pos, direction),
+        }
+    }
+}
+
 pub trait VecRadius<D>
 where
     Self: Sized,
@@ -283,3 +814,351 @@ impl<T> IndexMut<&Vec2D<usize>> for Array2<T> {
         self.index_mut((index.y, index.x))
     }
 }
+
+/// A vector of any type in 3D space
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Default)]
+pub struct Vec3D<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3D<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: CheckedAdd + CheckedSub, Rhs: TryIntoUnsigned + TryInto<T> + Copy> VecSum<Vec3D<Rhs>>
+    for Vec3D<T>
+{
+    /// Sum two vectors, returning None if any of the integers overflows.
+    fn vec_sum(&self, v: &Vec3D<Rhs>) -> Option<Self> {
+        Some(Self {
+            x: checked_add_signed(&self.x, v.x)?,
+            y: checked_add_signed(&self.y, v.y)?,
+            z: checked_add_signed(&self.z, v.z)?,
+        })
+    }
+}
+
+impl<T: CheckedMul> VecScale<T> for Vec3D<T> {
+    fn vec_scale(&self, factor: T) -> Option<Self> {
+        let x = self.x.checked_mul(&factor)?;
+        let y = self.y.checked_mul(&factor)?;
+        let z = self.z.checked_mul(&factor)?;
+        Some(Self { x, y, z })
+    }
+}
+
+impl<T: PartialOrd + Default> Vec3D<T> {
+    pub fn discard_out_of_bounds(self, lower_bound: &Self, upper_bound: &Self) -> Option<Self> {
+        Some(self).filter(|vec| {
+            vec.x >= lower_bound.x
+                && vec.x < upper_bound.x
+                && vec.y >= lower_bound.y
+                && vec.y < upper_bound.y
+                && vec.z >= lower_bound.z
+                && vec.z < upper_bound.z
+        })
+    }
+
+    pub fn bind_to_map(self, upper_bound: &Self) -> Option<Self> {
+        self.discard_out_of_bounds(&Self::default(), upper_bound)
+    }
+}
+
+impl<T: Euclid> Vec3D<T> {
+    pub fn wrap_out_of_bounds(&self, upper_bound: &Self) -> Self {
+        Self {
+            x: self.x.rem_euclid(&upper_bound.x),
+            y: self.y.rem_euclid(&upper_bound.y),
+            z: self.z.rem_euclid(&upper_bound.z),
+        }
+    }
+}
+
+impl<T: PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy> Vec3D<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Square of the Euclidean distance, which is exact for integers and
+    /// enough to compare distances.
+    pub fn squared_distance(&self, other: &Self) -> T {
+        let (x, y, z) = (
+            abs_diff(self.x, other.x),
+            abs_diff(self.y, other.y),
+            abs_diff(self.z, other.z),
+        );
+        x * x + y * y + z * z
+    }
+}
+
+impl<T: PartialOrd + Sub<Output = T> + ToPrimitive + Copy> Vec3D<T> {
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        [
+            abs_diff(self.x, other.x),
+            abs_diff(self.y, other.y),
+            abs_diff(self.z, other.z),
+        ]
+        .iter()
+        .map(|d| d.to_f64().unwrap_or(f64::NAN).powi(2))
+        .sum::<f64>()
+        .sqrt()
+    }
+}
+
+impl<T> Vec3D<T>
+where
+    Self: VecSum<Vec3D<i8>>,
+{
+    /// The 6 vectors that differ by one along a single axis.
+    /// Those that would overflow are skipped.
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .filter_map(move |(x, y, z)| self.vec_sum(&Vec3D::new(x, y, z)))
+    }
+
+    /// The 26 vectors that differ by at most one along every axis.
+    /// Those that would overflow are skipped.
+    pub fn all_neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Vec3D::new(x, y, z))))
+            .filter(|delta| *delta != Vec3D::default())
+            .filter_map(move |delta| self.vec_sum(&delta))
+    }
+}
+
+impl<T: FromStr> FromStr for Vec3D<T>
+where
+    DayError: From<T::Err>,
+{
+    type Err = DayError;
+
+    /// Parse comma separated coordinates, such as `1,2,3` or `19, 13, -30`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords = s
+            .split(',')
+            .map(|coord| coord.trim().parse::<T>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let [x, y, z] = <[T; 3]>::try_from(coords)
+            .map_err(|_| DayError::GenericParseErr("vector must have 3 coordinates"))?;
+        Ok(Vec3D { x, y, z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec2d_operators() {
+        let a = Vec2D::new(3i32, -2);
+        let b = Vec2D::new(1, 4);
+        assert_eq!(&a + &b, Vec2D::new(4, 2));
+        assert_eq!(a.clone() - b.clone(), Vec2D::new(2, -6));
+        assert_eq!(-a.clone(), Vec2D::new(-3, 2));
+        assert_eq!(a.clone() * 3, Vec2D::new(9, -6));
+
+        let mut c = a.clone();
+        c += b.clone();
+        c -= Vec2D::new(0, 2);
+        assert_eq!(c, Vec2D::new(4, 0));
+        assert_eq!(Vec2D::new(2usize, 3) + Vec2D::new(1, 1), Vec2D::new(3, 4));
+    }
+
+    #[test]
+    fn vec2d_helpers() {
+        let a = Vec2D::new(3i32, -2);
+        let b = Vec2D::new(1, 4);
+        assert_eq!(a.manhattan_distance(&b), 8);
+        assert_eq!(a.chebyshev_distance(&b), 6);
+        assert_eq!(
+            Vec2D::new(1usize, 7).manhattan_distance(&Vec2D::new(4, 2)),
+            8
+        );
+        assert_eq!(a.signum(), Vec2D::new(1, -1));
+        assert_eq!(a.dot(&b), -5);
+        assert_eq!(a.cross(&b), 14);
+        assert_eq!(a.rotate_cw().rotate_ccw(), a);
+        assert_eq!(a.rotate_cw().rotate_cw(), -a);
+
+        for direction in Direction::get_all() {
+            let vec = Vec2D::<i32>::from(direction);
+            assert_eq!(vec.rotate_cw(), Vec2D::from(direction.rotate_cw_90()));
+            assert_eq!(vec.rotate_ccw(), Vec2D::from(direction.rotate_ccw_90()));
+        }
+        let east = Vec2D::<i32>::from(Direction::East);
+        assert!(east.cross(&Vec2D::from(Direction::South)) > 0);
+    }
+
+    #[test]
+    fn vec3d_sum_and_scale() {
+        let v = Vec3D::new(1usize, 2, 3);
+        assert_eq!(v.vec_sum(&Vec3D::new(-1, 0, 2)), Some(Vec3D::new(0, 2, 5)));
+        assert_eq!(v.vec_sum(&Vec3D::new(0, -3, 0)), None);
+        assert_eq!(v.vec_scale(3), Some(Vec3D::new(3, 6, 9)));
+        assert_eq!(Vec3D::new(u8::MAX, 0, 0).vec_scale(2), None);
+    }
+
+    #[test]
+    fn vec3d_distances() {
+        let a = Vec3D::new(1u64, 5, 2);
+        let b = Vec3D::new(4u64, 1, 2);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.squared_distance(&b), 25);
+        assert_eq!(a.euclidean_distance(&b), 5.0);
+        assert_eq!(
+            Vec3D::new(-1i32, 0, 0).manhattan_distance(&Vec3D::new(1, -2, 3)),
+            7
+        );
+    }
+
+    #[test]
+    fn vec3d_neighbours() {
+        let v = Vec3D::new(2i32, 2, 2);
+        assert_eq!(v.orthogonal_neighbours().count(), 6);
+        assert!(v
+            .orthogonal_neighbours()
+            .all(|n| n.manhattan_distance(&v) == 1));
+        assert_eq!(v.all_neighbours().count(), 26);
+        assert!(!v.all_neighbours().any(|n| n == v));
+
+        let origin = Vec3D::<usize>::default();
+        assert_eq!(origin.orthogonal_neighbours().count(), 3);
+        assert_eq!(origin.all_neighbours().count(), 7);
+    }
+
+    #[test]
+    fn vec3d_bounds() {
+        let upper = Vec3D::new(3, 3, 3);
+        assert_eq!(
+            Vec3D::new(0, 2, 1).bind_to_map(&upper),
+            Some(Vec3D::new(0, 2, 1))
+        );
+        assert_eq!(Vec3D::new(0, 3, 1).bind_to_map(&upper), None);
+        assert_eq!(Vec3D::new(-1, 0, 0).bind_to_map(&upper), None);
+        assert_eq!(
+            Vec3D::new(-1, 4, 2).wrap_out_of_bounds(&upper),
+            Vec3D::new(2, 1, 2)
+        );
+    }
+
+    #[test]
+    fn vec3d_parse() -> Result<(), DayError> {
+        assert_eq!("1,2,3".parse::<Vec3D<u32>>()?, Vec3D::new(1, 2, 3));
+        assert_eq!(
+            "19, 13, -30".parse::<Vec3D<i64>>()?,
+            Vec3D::new(19, 13, -30)
+        );
+        assert!("1,2".parse::<Vec3D<i32>>().is_err());
+        assert!("1,2,3,4".parse::<Vec3D<i32>>().is_err());
+        assert!("1,x,3".parse::<Vec3D<i32>>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn direction_helpers() -> Result<(), DayError> {
+        assert_eq!(Direction::North.opposite(), Direction::South);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+        assert_eq!(Direction::West.rotate_cw_45(), Direction::NorthWest);
+        assert_eq!(Direction::North.rotate_ccw_45(), Direction::NorthWest);
+        assert!(Direction::East.is_orthogonal());
+        assert!(!Direction::SouthEast.is_orthogonal());
+        assert!(Direction::North < Direction::NorthEast);
+
+        assert_eq!(Direction::from_arrow('v')?, Direction::South);
+        assert!(Direction::from_arrow('x').is_err());
+        assert_eq!(Direction::from_letter('L')?, Direction::West);
+        assert_eq!(Direction::from_letter('N')?, Direction::North);
+        assert_eq!(Direction::East.to_arrow(), Some('>'));
+        assert_eq!(Direction::NorthEast.to_arrow(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn direction_set() {
+        let mut set = DirectionSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(Direction::East));
+        assert!(!set.insert(Direction::East));
+        set |= Direction::North;
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [Direction::North, Direction::East]
+        );
+
+        let orthogonal = DirectionSet::orthogonal();
+        assert!(set.is_subset(orthogonal));
+        assert_eq!((orthogonal - set).len(), 2);
+        assert_eq!(orthogonal & set, set);
+        assert_eq!(
+            (!orthogonal).iter().filter(|d| d.is_orthogonal()).count(),
+            0
+        );
+        assert_eq!(orthogonal | !orthogonal, DirectionSet::all());
+
+        assert!(set.remove(Direction::North));
+        assert!(!set.contains(Direction::North));
+        assert_eq!(set, DirectionSet::from(Direction::East));
+    }
+
+    #[test]
+    fn topologies() {
+        let size = Vec2D::new(3usize, 2);
+        let corner = Vec2D::new(0, 0);
+
+        let bounded = Bounded::new(size.clone());
+        assert_eq!(bounded.neighbours8(&corner).count(), 3);
+        assert_eq!(bounded.step(&corner, Direction::North), None);
+        assert_eq!(
+            bounded.step_by(&corner, Direction::East, 2),
+            Some((Vec2D::new(2, 0), Direction::East))
+        );
+
+        let toroidal = Toroidal::new(size.clone());
+        assert_eq!(toroidal.neighbours4(&corner).count(), 4);
+        assert_eq!(
+            toroidal.step(&corner, Direction::NorthWest),
+            Some((Vec2D::new(2, 1), Direction::NorthWest))
+        );
+        assert_eq!(
+            toroidal.translate(&corner, &Vec2D::new(-7, 5)),
+            Vec2D::new(2, 1)
+        );
+        for (x, delta) in [
+            (0, isize::MIN),
+            (2, isize::MAX),
+            (usize::MAX, 1),
+            (1, -4),
+            (2, 1),
+        ] {
+            assert_eq!(
+                toroidal
+                    .translate(&Vec2D::new(x, 0), &Vec2D::new(delta, 0))
+                    .x,
+                (x as i128 + delta as i128).rem_euclid(3) as usize
+            );
+        }
+
+        // leaving through the east edge leads to the top left corner, facing south
+        let edges = EdgeMap::new(size, |_: &Vec2D<usize>, direction| {
+            (direction == Direction::East).then_some((Vec2D::new(0, 0), Direction::South))
+        });
+        assert_eq!(
+            edges.step_by(&Vec2D::new(1, 1), Direction::East, 3),
+            Some((Vec2D::new(0, 1), Direction::South))
+        );
+        assert_eq!(edges.step(&corner, Direction::West), None);
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+
+use ndarray::Array2;
+
+use super::{Bounded, Direction, Topology, Vec2D};
+use crate::DayError;
+
+type Pos = Vec2D<usize>;
+
+/// Integer vector in the 3D space the net is folded into.
+type Vec3 = [i64; 3];
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vec3, k: i64) -> Vec3 {
+    a.map(|c| c * k)
+}
+
+fn dot(a: Vec3, b: Vec3) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Face of the cube, with the unit vectors its columns (`right`) and rows
+/// (`down`) follow once folded, and the one pointing out of the cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Face {
+    corner: Pos,
+    right: Vec3,
+    down: Vec3,
+    normal: Vec3,
+}
+
+impl Face {
+    /// Face reached by folding the net across the edge toward `direction`.
+    fn fold(&self, corner: Pos, direction: Direction) -> Face {
+        let (right, down, normal) = match direction {
+            Direction::East => (scale(self.normal, -1), self.down, self.right),
+            Direction::West => (self.normal, self.down, scale(self.right, -1)),
+            Direction::South => (self.right, scale(self.normal, -1), self.down),
+            Direction::North => (self.right, self.normal, scale(self.down, -1)),
+            _ => unreachable!("faces are only folded orthogonally"),
+        };
+        Face {
+            corner,
+            right,
+            down,
+            normal,
+        }
+    }
+
+    /// Unit vector of an orthogonal direction of the face.
+    fn direction_vec(&self, direction: Direction) -> Vec3 {
+        match direction {
+            Direction::East => self.right,
+            Direction::West => scale(self.right, -1),
+            Direction::South => self.down,
+            Direction::North => scale(self.down, -1),
+            _ => unreachable!("only orthogonal directions lie on a face"),
+        }
+    }
+
+    /// Orthogonal direction of the face whose vector is `vec`.
+    fn direction_of(&self, vec: Vec3) -> Direction {
+        Direction::get_all_orthogonal()
+            .find(|direction| self.direction_vec(*direction) == vec)
+            .expect("vector must lie on the face")
+    }
+}
+
+/// 2D map made of six square faces that fold into a cube, where stepping off
+/// the edge of a face leads onto the face glued to it on the cube.
+///
+/// Any of the eleven nets works, in any orientation: the gluing of the edges
+/// is found by folding the faces in 3D.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeNet {
+    face_size: usize,
+    faces: Vec<Face>,
+    /// Index of the face at every position of the net, by face-sized blocks.
+    blocks: Array2<Option<usize>>,
+}
+
+impl CubeNet {
+    /// Cube net of a map of the given size, where the tiles that belong to a
+    /// face match `is_on_net`.
+    pub fn new(size: Pos, is_on_net: impl Fn(&Pos) -> bool) -> Result<Self, DayError> {
+        let invalid = || DayError::GenericParseErr("map is not a net of a cube");
+
+        let tiles = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| Pos::new(x, y)))
+            .filter(|pos| is_on_net(pos))
+            .count();
+        let face_size = (tiles / 6).isqrt();
+        if face_size == 0 || 6 * face_size * face_size != tiles {
+            return Err(invalid());
+        }
+
+        let on_net = Array2::from_shape_fn(
+            (size.y.div_ceil(face_size), size.x.div_ceil(face_size)),
+            |(y, x)| is_on_net(&Pos::new(x * face_size, y * face_size)),
+        );
+        let first = on_net
+            .indexed_iter()
+            .find(|(_, on_net)| **on_net)
+            .map(|((y, x), _)| Pos::new(x, y))
+            .ok_or_else(invalid)?;
+
+        // fold the faces one by one, starting from the first one facing up
+        let mut blocks = Array2::from_elem(on_net.raw_dim(), None);
+        let mut faces = vec![Face {
+            corner: first.clone(),
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+            normal: [0, 0, 1],
+        }];
+        blocks[&first] = Some(0);
+        let net = Bounded::new(Pos::new(on_net.ncols(), on_net.nrows()));
+        let mut queue = VecDeque::from([0]);
+        while let Some(index) = queue.pop_front() {
+            let face = faces[index].clone();
+            for direction in Direction::get_all_orthogonal() {
+                let Some((block, _)) = net.step(&face.corner, direction) else {
+                    continue;
+                };
+                if !on_net[&block] || blocks[&block].is_some() {
+                    continue;
+                }
+                if faces
+                    .iter()
+                    .any(|other| other.normal == face.direction_vec(direction))
+                {
+                    // two faces are folded onto the same side
+                    return Err(invalid());
+                }
+                blocks[&block] = Some(faces.len());
+                queue.push_back(faces.len());
+                faces.push(face.fold(block, direction));
+            }
+        }
+        if faces.len() != 6 || on_net.iter().filter(|on_net| **on_net).count() != 6 {
+            return Err(invalid());
+        }
+
+        for face in faces.iter_mut() {
+            face.corner = Pos::new(face.corner.x * face_size, face.corner.y * face_size);
+        }
+
+        Ok(CubeNet {
+            face_size,
+            faces,
+            blocks,
+        })
+    }
+
+    /// Length of the sides of the faces.
+    pub fn face_size(&self) -> usize {
+        self.face_size
+    }
+
+    /// Index (from 0 to 5) of the face that contains `pos`, if any.
+    pub fn face_of(&self, pos: &Pos) -> Option<usize> {
+        self.blocks
+            .get((pos.y / self.face_size, pos.x / self.face_size))
+            .copied()
+            .flatten()
+    }
+
+    /// Center of the tile at `pos` of a face, in a cube of side
+    /// `2 * face_size` centered on the origin, so that tiles are 2 apart.
+    fn tile_center(&self, face: &Face, pos: &Pos) -> Vec3 {
+        let size = self.face_size as i64;
+        let local_x = (pos.x - face.corner.x) as i64;
+        let local_y = (pos.y - face.corner.y) as i64;
+        add(
+            scale(face.normal, size),
+            add(
+                scale(face.right, 2 * local_x + 1 - size),
+                scale(face.down, 2 * local_y + 1 - size),
+            ),
+        )
+    }
+
+    /// Inverse of [`CubeNet::tile_center`].
+    fn tile_at(&self, face: &Face, center: Vec3) -> Pos {
+        let size = self.face_size as i64;
+        let local = |axis: Vec3| ((dot(center, axis) + size - 1) / 2) as usize;
+        Pos::new(
+            face.corner.x + local(face.right),
+            face.corner.y + local(face.down),
+        )
+    }
+}
+
+impl Topology for CubeNet {
+    /// Only orthogonal steps are possible, diagonal ones lead nowhere.
+    fn step(&self, pos: &Pos, direction: Direction) -> Option<(Pos, Direction)> {
+        if !direction.is_orthogonal() {
+            return None;
+        }
+        let size = self.face_size as i64;
+        let face = &self.faces[self.face_of(pos)?];
+        let direction_vec = face.direction_vec(direction);
+        let center = self.tile_center(face, pos);
+
+        if dot(center, direction_vec) + 2 < size {
+            return Some((
+                self.tile_at(face, add(center, scale(direction_vec, 2))),
+                direction,
+            ));
+        }
+
+        // across the edge, then down the side of the cube it leads to
+        let inwards = scale(face.normal, -1);
+        let new_face = self
+            .faces
+            .iter()
+            .find(|other| other.normal == direction_vec)
+            .expect("every side of the cube has a face");
+        Some((
+            self.tile_at(new_face, add(center, add(direction_vec, inwards))),
+            new_face.direction_of(inwards),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn net(rows: &[&str]) -> Result<CubeNet, DayError> {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        CubeNet::new(Pos::new(width, rows.len()), |pos| {
+            rows[pos.y]
+                .as_bytes()
+                .get(pos.x)
+                .is_some_and(|c| *c == b'#')
+        })
+    }
+
+    #[test]
+    fn gluing() -> Result<(), DayError> {
+        //   A
+        // BCD
+        //   EF
+        let cube = net(&[
+            "    ##", "    ##", "######", "######", "    ####", "    ####",
+        ])?;
+        assert_eq!(cube.face_size(), 2);
+        assert_eq!(cube.face_of(&Pos::new(1, 0)), None);
+
+        // inside a face, and onto an adjacent face of the net
+        assert_eq!(
+            cube.step(&Pos::new(4, 1), Direction::South),
+            Some((Pos::new(4, 2), Direction::South))
+        );
+        // top of A is glued to the top of B, upside down
+        assert_eq!(
+            cube.step(&Pos::new(4, 0), Direction::North),
+            Some((Pos::new(1, 2), Direction::South))
+        );
+        // right of A is glued to the right of F, upside down
+        assert_eq!(
+            cube.step(&Pos::new(5, 0), Direction::East),
+            Some((Pos::new(7, 5), Direction::West))
+        );
+        // right of D is glued to the top of F
+        assert_eq!(
+            cube.step(&Pos::new(5, 3), Direction::East),
+            Some((Pos::new(6, 4), Direction::South))
+        );
+        assert_eq!(cube.step(&Pos::new(5, 3), Direction::SouthEast), None);
+
+        // walking straight around the cube leads back to the start
+        for direction in Direction::get_all_orthogonal() {
+            let start = Pos::new(4, 3);
+            assert_eq!(
+                cube.step_by(&start, direction, 8).map(|(pos, _)| pos),
+                Some(start)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_nets() {
+        assert!(net(&["######"]).is_err());
+        assert!(net(&["###", "###"]).is_err());
+        assert!(net(&["##", "##", "##"]).is_err());
+        assert!(net(&[]).is_err());
+    }
+}
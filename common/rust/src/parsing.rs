@@ -1,26 +1,99 @@
+use std::str::FromStr;
+
 use itertools::Itertools;
 use ndarray::Array2;
 
-use crate::{navigation::Vec2D, DayError};
+use crate::{navigation::Vec2D, DayError, InputLocation};
+
+/// Line of the puzzle input together with its line number, so that errors
+/// can point back to where they happened in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLine {
+    /// Line number, starting at 1.
+    pub number: usize,
+    pub text: String,
+}
+
+impl InputLine {
+    pub fn location(&self) -> InputLocation {
+        InputLocation {
+            line: self.number,
+            column: None,
+            snippet: self.text.clone(),
+        }
+    }
+
+    /// Location of the character at index `x` (starting at 0) of the line.
+    pub fn location_at(&self, x: usize) -> InputLocation {
+        InputLocation {
+            column: Some(x + 1),
+            ..self.location()
+        }
+    }
+
+    /// Parse the line, recording its location in the error on failure.
+    pub fn parse<T: FromStr>(&self) -> Result<T, DayError>
+    where
+        T::Err: Into<DayError>,
+    {
+        self.parse_with(str::parse)
+    }
+
+    /// Parse the line with the given function, recording its location in the
+    /// error on failure.
+    pub fn parse_with<T, E: Into<DayError>>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<T, DayError> {
+        parse(&self.text).map_err(|err| err.into().at(self.location()))
+    }
+}
+
+/// Number the lines of the input, starting at 1.
+pub fn numbered_lines(lines: impl Iterator<Item = String>) -> impl Iterator<Item = InputLine> {
+    lines.enumerate().map(|(i, text)| InputLine {
+        number: i + 1,
+        text,
+    })
+}
+
+/// Parse every line of the input, recording the location of the line that
+/// failed to parse in the error.
+pub fn parse_lines<T: FromStr>(lines: impl Iterator<Item = String>) -> Result<Vec<T>, DayError>
+where
+    T::Err: Into<DayError>,
+{
+    numbered_lines(lines).map(|line| line.parse()).collect()
+}
 
+/// Parse a rectangular map, one character per cell.
+/// A final empty line, as left by a trailing newline, is ignored.
 pub fn try_parse_2d_array<T: TryFrom<char>>(
     lines: impl Iterator<Item = String>,
 ) -> Result<Array2<T>, DayError> {
-    let mut lines = lines.peekable();
+    let mut lines = numbered_lines(lines).peekable();
     let width = lines
         .peek()
         .ok_or(DayError::GenericParseErr("input is empty"))?
+        .text
         .len();
-    let board = lines
-        .flat_map(|row| {
-            row.chars()
-                .map(|c| {
-                    T::try_from(c)
-                        .map_err(|_| DayError::GenericParseErr("can't parse character in map"))
-                })
-                .collect_vec()
-        })
-        .process_results(|it| it.collect_vec())?;
+    let mut board = Vec::new();
+    while let Some(line) = lines.next() {
+        if line.text.is_empty() && lines.peek().is_none() {
+            break;
+        }
+        if line.text.len() != width {
+            return Err(DayError::GenericParseErr(
+                "lines in input have different lengths, while a rectangle input was expected",
+            )
+            .at(line.location()));
+        }
+        for (x, c) in line.text.chars().enumerate() {
+            board.push(T::try_from(c).map_err(|_| {
+                DayError::GenericParseErr("can't parse character in map").at(line.location_at(x))
+            })?);
+        }
+    }
 
     Array2::from_shape_vec((board.len() / width, width), board).map_err(|_| {
         DayError::GenericParseErr(
@@ -40,21 +113,25 @@ where
 pub fn try_parse_sparse_2d_array<T: MaybeParseChar>(
     lines: impl Iterator<Item = String>,
 ) -> Result<(Vec<T>, Vec2D<usize>), DayError> {
-    let mut lines = lines.peekable();
+    let mut lines = numbered_lines(lines).peekable();
     let width = lines
         .peek()
         .ok_or(DayError::GenericParseErr("input is empty"))?
+        .text
         .len();
     let mut height = 0;
     let list = lines
         .enumerate()
-        .flat_map(|(y, row)| {
+        .flat_map(|(y, line)| {
             height += 1;
-            row.chars()
+            line.text
+                .chars()
                 .enumerate()
                 .map(|(x, c)| {
-                    T::maybe_parse_char(Vec2D::<usize>::new(x, y), c)
-                        .map_err(|_| DayError::GenericParseErr("can't parse character in map"))
+                    T::maybe_parse_char(Vec2D::<usize>::new(x, y), c).map_err(|_| {
+                        DayError::GenericParseErr("can't parse character in map")
+                            .at(line.location_at(x))
+                    })
                 })
                 .collect_vec()
         })
@@ -62,3 +139,87 @@ pub fn try_parse_sparse_2d_array<T: MaybeParseChar>(
 
     Ok((list, Vec2D::<usize>::new(width, height)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorContext;
+
+    fn lines<'a>(lines: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+        lines.iter().map(|x| String::from(*x))
+    }
+
+    #[derive(Debug)]
+    struct Tile;
+
+    impl TryFrom<char> for Tile {
+        type Error = DayError;
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            match value {
+                '.' => Ok(Tile),
+                _ => Err(DayError::TryFromCharErr("Tile")),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_lines_location() {
+        let err = parse_lines::<u32>(lines(&["1", "2", "x3"])).unwrap_err();
+        assert!(matches!(err.root(), DayError::NumParseErr(_)));
+        assert_eq!(
+            err.location(),
+            Some(&InputLocation {
+                line: 3,
+                column: None,
+                snippet: String::from("x3"),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_2d_array_location() {
+        let err = try_parse_2d_array::<Tile>(lines(&["...", ".#."])).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!((location.line, location.column), (2, Some(2)));
+
+        let err = try_parse_2d_array::<Tile>(lines(&["...", "....", "..."])).unwrap_err();
+        assert_eq!(err.location().unwrap().line, 2);
+
+        let err = try_parse_2d_array::<Tile>(lines(&["...", "", "..."])).unwrap_err();
+        assert_eq!(err.location().unwrap().line, 2);
+    }
+
+    #[test]
+    fn parse_2d_array_trailing_empty_line() -> Result<(), DayError> {
+        let array = try_parse_2d_array::<Tile>(lines(&["...", "...", ""]))?;
+        assert_eq!(array.dim(), (2, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn context_display() {
+        let err = numbered_lines(lines(&["a AND b", "a XOR"]))
+            .map(|line| {
+                line.parse_with(|text| {
+                    text.split(' ')
+                        .nth(2)
+                        .map(String::from)
+                        .ok_or(DayError::GenericParseErr("can't get right input"))
+                })
+                .context("while parsing gate")
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .context("while parsing section 2")
+            .unwrap_err();
+
+        assert_eq!(
+            err.contexts(),
+            ["while parsing gate", "while parsing section 2"]
+        );
+        assert_eq!(
+            err.to_string(),
+            "can't get right input\n  at line 2: \"a XOR\"\n  while parsing gate\n  while parsing section 2"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use ndarray::Array2;
+use num_traits::{PrimInt, Signed, ToPrimitive};
+
+use crate::navigation::{Direction, Vec2D};
+use crate::DayError;
+
+/// Coordinate widened so that products of coordinates can't overflow.
+fn wide<T: ToPrimitive>(value: T) -> i128 {
+    value.to_i128().expect("coordinate must fit in an i128")
+}
+
+fn narrow<T: PrimInt>(value: i128) -> T {
+    T::from(value).expect("result must fit in the coordinate type")
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Simple polygon with vertices on integer coordinates, given in order
+/// around the polygon, either clockwise or counterclockwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<Vec2D<T>>,
+}
+
+impl<T: PrimInt> Polygon<T> {
+    pub fn new(vertices: Vec<Vec2D<T>>) -> Self {
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Vec2D<T>] {
+        &self.vertices
+    }
+
+    /// Every edge of the polygon, from a vertex to the next one.
+    pub fn edges(&self) -> impl Iterator<Item = (&Vec2D<T>, &Vec2D<T>)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// Whether every edge is either horizontal or vertical.
+    pub fn is_rectilinear(&self) -> bool {
+        self.edges().all(|(a, b)| a.x == b.x || a.y == b.y)
+    }
+
+    /// Twice the area of the polygon, using the shoelace formula. The area
+    /// of a polygon on integer coordinates is a multiple of one half.
+    pub fn double_area(&self) -> T {
+        let sum: i128 = self
+            .edges()
+            .map(|(a, b)| wide(a.x) * wide(b.y) - wide(b.x) * wide(a.y))
+            .sum();
+        narrow(sum.abs())
+    }
+
+    /// Number of integer points on the edges of the polygon.
+    pub fn boundary_points(&self) -> T {
+        narrow(
+            self.edges()
+                .map(|(a, b)| gcd(wide(b.x) - wide(a.x), wide(b.y) - wide(a.y)))
+                .sum(),
+        )
+    }
+
+    /// Number of integer points strictly inside the polygon, using Pick's
+    /// theorem.
+    pub fn interior_points(&self) -> T {
+        let double_area = wide(self.double_area());
+        let boundary = wide(self.boundary_points());
+        narrow((double_area - boundary + 2) / 2)
+    }
+
+    /// Number of integer points inside the polygon or on its edges.
+    pub fn lattice_points(&self) -> T {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether `point` is inside the polygon or on its edges.
+    pub fn contains(&self, point: &Vec2D<T>) -> bool {
+        let (x, y) = (wide(point.x), wide(point.y));
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            let (ax, ay, bx, by) = (wide(a.x), wide(a.y), wide(b.x), wide(b.y));
+            let cross = (bx - ax) * (y - ay) - (by - ay) * (x - ax);
+            if cross == 0
+                && ax.min(bx) <= x
+                && x <= ax.max(bx)
+                && ay.min(by) <= y
+                && y <= ay.max(by)
+            {
+                return true;
+            }
+            // cast a ray toward positive x, counting the edges it crosses
+            if (ay > y) != (by > y) && (cross > 0) == (by > ay) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+impl<T: PrimInt + Signed> Polygon<T> {
+    /// Polygon traced by walking from `start`, going the given distance in
+    /// every direction in turn, until getting back to `start`.
+    pub fn from_steps(start: Vec2D<T>, steps: impl IntoIterator<Item = (Direction, T)>) -> Self {
+        let mut vertices = vec![start];
+        for (direction, distance) in steps {
+            let last = vertices[vertices.len() - 1].clone();
+            vertices.push(last + Vec2D::from(direction) * distance);
+        }
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+}
+
+/// Integer points inside or on the edges of a rectilinear polygon, to check
+/// whether a rectangle fits in the polygon without going through all its
+/// points.
+///
+/// Along each axis, the coordinates of the vertices split the plane into
+/// lines and the gaps between them, and all the points of a line crossing a
+/// gap (or a gap crossing a gap) are either in the polygon or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearRegion<T> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    /// Number of blocks outside of the polygon, summed over the blocks with
+    /// smaller or equal indices along both axes.
+    outside: Array2<usize>,
+}
+
+impl<T: PrimInt> RectilinearRegion<T> {
+    pub fn new(polygon: &Polygon<T>) -> Result<Self, DayError> {
+        if !polygon.is_rectilinear() {
+            return Err(DayError::GenericParseErr("polygon is not rectilinear"));
+        }
+        let coordinates = |axis: fn(&Vec2D<T>) -> T| {
+            let mut values: Vec<T> = polygon.vertices().iter().map(axis).collect();
+            values.sort();
+            values.dedup();
+            values
+        };
+        let xs = coordinates(|v| v.x);
+        let ys = coordinates(|v| v.y);
+        let mut region = RectilinearRegion {
+            outside: Array2::zeros((2 * ys.len(), 2 * xs.len())),
+            xs,
+            ys,
+        };
+
+        let mut on_edge = Array2::from_elem(region.outside.raw_dim(), false);
+        let mut vertical_edges = Vec::new();
+        for (a, b) in polygon.edges() {
+            let (Some(x1), Some(x2), Some(y1), Some(y2)) = (
+                region.x_block(a.x),
+                region.x_block(b.x),
+                region.y_block(a.y),
+                region.y_block(b.y),
+            ) else {
+                unreachable!("vertices are on block lines");
+            };
+            for y in y1.min(y2)..=y1.max(y2) {
+                for x in x1.min(x2)..=x1.max(x2) {
+                    on_edge[(y, x)] = true;
+                }
+            }
+            if a.x == b.x && a.y != b.y {
+                vertical_edges.push((a.x, a.y.min(b.y), a.y.max(b.y)));
+            }
+        }
+
+        for y in 0..region.outside.nrows() {
+            let y_value = Self::block_start(&region.ys, y);
+            // x of the edges crossed by a ray toward positive x
+            let mut crossings: Vec<T> = vertical_edges
+                .iter()
+                .filter(|(_, from, to)| *from <= y_value && y_value < *to)
+                .map(|(x, _, _)| *x)
+                .collect();
+            crossings.sort();
+            let mut crossed = 0;
+            for x in 0..region.outside.ncols() {
+                let x_value = Self::block_start(&region.xs, x);
+                while crossed < crossings.len() && crossings[crossed] < x_value {
+                    crossed += 1;
+                }
+                let is_empty =
+                    Self::is_empty_gap(&region.xs, x) || Self::is_empty_gap(&region.ys, y);
+                let is_outside = !on_edge[(y, x)] && crossed % 2 == 0 && !is_empty;
+                region.outside[(y, x)] = usize::from(is_outside);
+            }
+        }
+
+        // sum up the blocks outside of the polygon
+        for axis in 0..2 {
+            region
+                .outside
+                .accumulate_axis_inplace(ndarray::Axis(axis), |previous, current| {
+                    *current += *previous
+                });
+        }
+        Ok(region)
+    }
+
+    /// Index of the block that contains `value`, where block `2 * i` is the
+    /// line at the `i`th coordinate and block `2 * i + 1` is the gap after it.
+    /// Values before the first line or after the last one are outside of the
+    /// polygon.
+    fn block(coordinates: &[T], value: T) -> Option<usize> {
+        match coordinates.binary_search(&value) {
+            Ok(i) => Some(2 * i),
+            Err(i) if i > 0 && i < coordinates.len() => Some(2 * i - 1),
+            Err(_) => None,
+        }
+    }
+
+    fn x_block(&self, x: T) -> Option<usize> {
+        Self::block(&self.xs, x)
+    }
+
+    fn y_block(&self, y: T) -> Option<usize> {
+        Self::block(&self.ys, y)
+    }
+
+    /// Smallest value in a block.
+    fn block_start(coordinates: &[T], block: usize) -> T {
+        coordinates[block / 2] + T::from(block % 2).expect("block parity is 0 or 1")
+    }
+
+    /// Whether a block is a gap between two consecutive lines, or after the
+    /// last line, which has no integer point.
+    fn is_empty_gap(coordinates: &[T], block: usize) -> bool {
+        block % 2 == 1
+            && coordinates
+                .get(block / 2 + 1)
+                .is_none_or(|next| *next - coordinates[block / 2] == T::one())
+    }
+
+    /// Number of blocks outside of the polygon in a range of blocks, both
+    /// inclusive.
+    fn outside_blocks(&self, (y1, x1): (usize, usize), (y2, x2): (usize, usize)) -> usize {
+        let sum = |y: Option<usize>, x: Option<usize>| match (y, x) {
+            (Some(y), Some(x)) => self.outside[(y, x)],
+            _ => 0,
+        };
+        sum(Some(y2), Some(x2)) + sum(y1.checked_sub(1), x1.checked_sub(1))
+            - sum(y1.checked_sub(1), Some(x2))
+            - sum(Some(y2), x1.checked_sub(1))
+    }
+
+    /// Whether `point` is inside the polygon or on its edges.
+    pub fn contains(&self, point: &Vec2D<T>) -> bool {
+        self.contains_rectangle(point, point)
+    }
+
+    /// Whether every integer point of the rectangle with opposite corners
+    /// `a` and `b` (both included) is inside the polygon or on its edges.
+    pub fn contains_rectangle(&self, a: &Vec2D<T>, b: &Vec2D<T>) -> bool {
+        let blocks = (
+            self.y_block(a.y.min(b.y)),
+            self.x_block(a.x.min(b.x)),
+            self.y_block(a.y.max(b.y)),
+            self.x_block(a.x.max(b.x)),
+        );
+        let (Some(y1), Some(x1), Some(y2), Some(x2)) = blocks else {
+            return false;
+        };
+        self.outside_blocks((y1, x1), (y2, x2)) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lattice_points() {
+        let triangle = Polygon::new(vec![Vec2D::new(0, 0), Vec2D::new(4, 0), Vec2D::new(0, 4)]);
+        assert_eq!(triangle.double_area(), 16);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+        assert_eq!(triangle.lattice_points(), 15);
+        assert!(triangle.contains(&Vec2D::new(2, 2)));
+        assert!(triangle.contains(&Vec2D::new(1, 1)));
+        assert!(!triangle.contains(&Vec2D::new(3, 2)));
+
+        // the trench of the example of 2023 day 18
+        let steps = [
+            (Direction::East, 6),
+            (Direction::South, 5),
+            (Direction::West, 2),
+            (Direction::South, 2),
+            (Direction::East, 2),
+            (Direction::South, 2),
+            (Direction::West, 5),
+            (Direction::North, 2),
+            (Direction::West, 1),
+            (Direction::North, 2),
+            (Direction::East, 2),
+            (Direction::North, 3),
+            (Direction::West, 2),
+            (Direction::North, 2),
+        ];
+        let trench = Polygon::from_steps(Vec2D::new(0i64, 0), steps);
+        assert_eq!(trench.vertices().len(), 14);
+        assert!(trench.is_rectilinear());
+        assert_eq!(trench.boundary_points(), 38);
+        assert_eq!(trench.lattice_points(), 62);
+    }
+
+    #[test]
+    fn rectilinear_region() -> Result<(), DayError> {
+        // ........
+        // .....###
+        // .....###
+        // ..######
+        // ..######
+        // ..######
+        // .....###
+        // .....###
+        let polygon = Polygon::new(
+            [
+                (5, 1),
+                (7, 1),
+                (7, 7),
+                (5, 7),
+                (5, 5),
+                (2, 5),
+                (2, 3),
+                (5, 3),
+            ]
+            .into_iter()
+            .map(|(x, y)| Vec2D::new(x, y))
+            .collect(),
+        );
+        let region = RectilinearRegion::new(&polygon)?;
+        for y in 0..10 {
+            for x in 0..10 {
+                let point = Vec2D::new(x, y);
+                assert_eq!(region.contains(&point), polygon.contains(&point));
+            }
+        }
+        assert!(region.contains_rectangle(&Vec2D::new(2, 3), &Vec2D::new(7, 5)));
+        assert!(region.contains_rectangle(&Vec2D::new(7, 1), &Vec2D::new(5, 7)));
+        assert!(!region.contains_rectangle(&Vec2D::new(2, 3), &Vec2D::new(7, 7)));
+        assert!(!region.contains_rectangle(&Vec2D::new(7, 7), &Vec2D::new(8, 7)));
+
+        // a notch of width 1 has no point outside of the polygon
+        let notched: Vec<Vec2D<u32>> = [
+            (0, 0),
+            (3, 0),
+            (3, 2),
+            (4, 2),
+            (4, 0),
+            (6, 0),
+            (6, 3),
+            (0, 3),
+        ]
+        .into_iter()
+        .map(|(x, y)| Vec2D::new(x, y))
+        .collect();
+        let region = RectilinearRegion::new(&Polygon::new(notched))?;
+        assert!(region.contains_rectangle(&Vec2D::new(0, 0), &Vec2D::new(6, 3)));
+
+        let triangle = Polygon::new(vec![Vec2D::new(0, 0), Vec2D::new(4, 0), Vec2D::new(0, 4)]);
+        assert!(RectilinearRegion::new(&triangle).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,818 @@
+use std::cmp::Reverse;
+use std::collections::{hash_map::Entry, BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+use num_traits::Zero;
+use rustc_hash::FxHashMap;
+
+use crate::grid::Grid;
+use crate::navigation::{Direction, Topology, Vec2D};
+
+type Pos = Vec2D<usize>;
+
+/// State of a search on a grid, which is at a position of the grid along
+/// with anything else that decides where it can go from there.
+pub trait GridState: Clone + Hash + Eq {
+    fn pos(&self) -> &Pos;
+}
+
+impl GridState for Pos {
+    fn pos(&self) -> &Pos {
+        self
+    }
+}
+
+/// Walker on a grid that faces a direction, and can only go forward or turn
+/// by 90 degrees.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Walker {
+    pub pos: Pos,
+    pub direction: Direction,
+    /// Number of steps forward since the last turn, counted only as far as
+    /// the limits of the [`WalkCosts`] need, so that walkers which can move
+    /// the same way are the same state.
+    pub run: usize,
+}
+
+impl Walker {
+    pub fn new(pos: Pos, direction: Direction) -> Self {
+        Walker {
+            pos,
+            direction,
+            run: 0,
+        }
+    }
+}
+
+impl GridState for Walker {
+    fn pos(&self) -> &Pos {
+        &self.pos
+    }
+}
+
+/// State of a search where the grid changes over time, so that the same
+/// state at different times leads to different places.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Timed<S> {
+    pub state: S,
+    pub time: usize,
+}
+
+impl<S> Timed<S> {
+    pub fn new(state: S, time: usize) -> Self {
+        Timed { state, time }
+    }
+}
+
+impl<S: GridState> GridState for Timed<S> {
+    fn pos(&self) -> &Pos {
+        self.state.pos()
+    }
+}
+
+/// Cost model of the moves of a [`Walker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkCosts<C> {
+    /// Cost of turning by 90 degrees, on top of the cost of the step if
+    /// turning isn't done in place.
+    pub turn: C,
+    /// Whether turning is a move of its own, or is always done along with a
+    /// step forward in the new direction.
+    pub turn_in_place: bool,
+    /// Number of steps forward needed after a turn before turning again.
+    pub min_run: usize,
+    /// Largest number of steps forward in a row.
+    pub max_run: usize,
+}
+
+impl<C: Copy + Zero> WalkCosts<C> {
+    /// Walker that may turn anywhere, paying `turn` for it.
+    pub fn new(turn: C) -> Self {
+        WalkCosts {
+            turn,
+            turn_in_place: true,
+            min_run: 0,
+            max_run: usize::MAX,
+        }
+    }
+
+    /// Largest run that makes a difference to the moves of a walker: any run
+    /// of at least `min_run` can turn, so it's only counted further when
+    /// there's a `max_run`.
+    fn run_cap(&self) -> usize {
+        if self.max_run == usize::MAX {
+            self.min_run
+        } else {
+            self.max_run
+        }
+    }
+
+    /// Moves of a walker on a grid with the given topology, along with their
+    /// cost, where `step_cost` is the cost of stepping onto a position, or
+    /// `None` if it can't be stepped onto.
+    pub fn moves(
+        &self,
+        walker: &Walker,
+        topology: &impl Topology,
+        mut step_cost: impl FnMut(&Pos) -> Option<C>,
+    ) -> impl Iterator<Item = (Walker, C)> {
+        let mut step = |direction: Direction, run: usize, cost: C| {
+            let (pos, direction) = topology.step(&walker.pos, direction)?;
+            let cost = cost + step_cost(&pos)?;
+            Some((
+                Walker {
+                    pos,
+                    direction,
+                    run: run.min(self.run_cap()),
+                },
+                cost,
+            ))
+        };
+
+        // forward, then both turns
+        let mut moves = [None, None, None];
+        if walker.run < self.max_run {
+            moves[0] = step(walker.direction, walker.run + 1, C::zero());
+        }
+        if walker.run >= self.min_run {
+            let turns = [
+                walker.direction.rotate_cw_90(),
+                walker.direction.rotate_ccw_90(),
+            ];
+            for (turn, direction) in moves[1..].iter_mut().zip(turns) {
+                *turn = if self.turn_in_place {
+                    Some((Walker::new(walker.pos.clone(), direction), self.turn))
+                } else {
+                    step(direction, 1, self.turn)
+                };
+            }
+        }
+        moves.into_iter().flatten()
+    }
+}
+
+/// States found by a search, with the cheapest known cost to each of them.
+struct Explored<N, C> {
+    states: Vec<N>,
+    indices: FxHashMap<N, usize>,
+    costs: Vec<C>,
+    /// State from which each state is reached at its cheapest known cost.
+    parents: Vec<Option<usize>>,
+    /// Other states from which some states are reached at the same cost,
+    /// only kept when searching for every path.
+    other_parents: FxHashMap<usize, Vec<usize>>,
+}
+
+impl<N: Clone + Hash + Eq, C: Zero + Ord + Copy> Explored<N, C> {
+    /// Search from the `starts` until a goal is found, or until the states
+    /// run out if there's no goal. Returns the goals at the cheapest cost,
+    /// which are all of them only if `all_paths` is set.
+    fn search<I: IntoIterator<Item = (N, C)>>(
+        starts: impl IntoIterator<Item = N>,
+        mut successors: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut goal: Option<impl FnMut(&N) -> bool>,
+        all_paths: bool,
+    ) -> (Self, Vec<usize>) {
+        let mut explored = Explored {
+            states: Vec::new(),
+            indices: FxHashMap::default(),
+            costs: Vec::new(),
+            parents: Vec::new(),
+            other_parents: FxHashMap::default(),
+        };
+        let mut expanded = Vec::new();
+        let mut heap = BinaryHeap::new();
+        for start in starts {
+            let (index, new) = explored.find_or_insert(start, C::zero(), None);
+            if new {
+                expanded.push(false);
+                heap.push(Reverse((
+                    heuristic(&explored.states[index]),
+                    C::zero(),
+                    index,
+                )));
+            }
+        }
+
+        let mut goals = Vec::new();
+        let mut goal_cost = None;
+        while let Some(Reverse((estimate, cost, index))) = heap.pop() {
+            if expanded[index] || cost > explored.costs[index] {
+                continue;
+            }
+            if goal_cost.is_some_and(|goal_cost| estimate > goal_cost) {
+                break;
+            }
+            expanded[index] = true;
+            if let Some(goal) = &mut goal {
+                if goal(&explored.states[index]) {
+                    goal_cost = Some(cost);
+                    goals.push(index);
+                    if !all_paths {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
+            for (next, step_cost) in successors(&explored.states[index]) {
+                let next_cost = cost + step_cost;
+                let (next, new) = explored.find_or_insert(next, next_cost, Some(index));
+                if new {
+                    expanded.push(false);
+                } else if next_cost < explored.costs[next] {
+                    explored.costs[next] = next_cost;
+                    explored.parents[next] = Some(index);
+                    explored.other_parents.remove(&next);
+                } else {
+                    // a state is only expanded once all its cheapest parents are, except
+                    // for parents reached by moves that cost nothing
+                    if next_cost == explored.costs[next] && all_paths && !expanded[next] {
+                        explored.other_parents.entry(next).or_default().push(index);
+                    }
+                    continue;
+                }
+                let estimate = next_cost + heuristic(&explored.states[next]);
+                heap.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+        (explored, goals)
+    }
+
+    /// Index of a state, which is added with the given cost and parent if
+    /// it's new. Returns whether it was.
+    fn find_or_insert(&mut self, state: N, cost: C, parent: Option<usize>) -> (usize, bool) {
+        match self.indices.entry(state) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let index = self.states.len();
+                self.states.push(entry.key().clone());
+                entry.insert(index);
+                self.costs.push(cost);
+                self.parents.push(parent);
+                (index, true)
+            }
+        }
+    }
+
+    /// Cheapest path to a state, from one of the starts.
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.states[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.states[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn into_costs(self) -> FxHashMap<N, C> {
+        self.states.into_iter().zip(self.costs).collect()
+    }
+}
+
+/// Cheapest path from one of the `starts` to a state that matches `goal`,
+/// along with its cost, found with Dijkstra's algorithm. Costs can't be
+/// negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Hash + Eq,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::zero(), goal)
+}
+
+/// Cheapest path from one of the `starts` to a state that matches `goal`,
+/// along with its cost, found with A*. The `heuristic` must never be more
+/// than the cost to the closest goal, nor decrease by more than the cost of
+/// a move.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Hash + Eq,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (explored, goals) = Explored::search(starts, successors, heuristic, Some(goal), false);
+    let &end = goals.first()?;
+    Some((explored.path_to(end), explored.costs[end]))
+}
+
+/// Every cheapest path from one of the `starts` to a state that matches
+/// `goal`, along with their cost, found with A* (see [`astar`]). Paths may be
+/// missed if they only differ by moves that cost nothing.
+pub fn astar_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(OptimalPaths<N>, C)>
+where
+    N: Clone + Hash + Eq,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (explored, ends) = Explored::search(starts, successors, heuristic, Some(goal), true);
+    let cost = explored.costs[*ends.first()?];
+    Some((
+        OptimalPaths {
+            states: explored.states,
+            parents: explored.parents,
+            other_parents: explored.other_parents,
+            ends,
+        },
+        cost,
+    ))
+}
+
+/// Cheapest cost to every state that can be reached from the `starts`.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> FxHashMap<N, C>
+where
+    N: Clone + Hash + Eq,
+    C: Zero + Ord + Copy,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let no_goal: Option<fn(&N) -> bool> = None;
+    let (explored, _) = Explored::search(starts, successors, |_| C::zero(), no_goal, false);
+    explored.into_costs()
+}
+
+/// Shortest path from one of the `starts` to a state that matches `goal`,
+/// where every move costs the same, found with a breadth-first search.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Hash + Eq,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: FxHashMap<N, Option<N>> = FxHashMap::default();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if goal(&state) {
+            let mut path = vec![state];
+            while let Some(Some(parent)) = parents.get(&path[path.len() - 1]) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in successors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Number of moves to every state that can be reached from the `starts`,
+/// where every move costs the same.
+pub fn bfs_all<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> FxHashMap<N, usize>
+where
+    N: Clone + Hash + Eq,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = FxHashMap::default();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state] + 1;
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Cheapest cost to every position of a grid of the given size, among the
+/// costs of the states at that position.
+pub fn distance_grid<N: GridState, C: Ord + Copy>(
+    distances: &FxHashMap<N, C>,
+    size: Pos,
+) -> Grid<Option<C>> {
+    let mut grid = Grid::from_elem(size, None);
+    for (state, &cost) in distances {
+        if let Some(best) = grid.get_mut(state.pos()) {
+            *best = Some(best.map_or(cost, |best: C| best.min(cost)));
+        }
+    }
+    grid
+}
+
+/// Every cheapest path of a search, see [`astar_all`].
+#[derive(Debug, Clone)]
+pub struct OptimalPaths<N> {
+    states: Vec<N>,
+    parents: Vec<Option<usize>>,
+    other_parents: FxHashMap<usize, Vec<usize>>,
+    ends: Vec<usize>,
+}
+
+impl<N: Clone> OptimalPaths<N> {
+    /// States from which a state is reached on the paths.
+    fn parents_of(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let others = self.other_parents.get(&index).into_iter().flatten();
+        self.parents[index].into_iter().chain(others.copied())
+    }
+
+    /// Goals that the paths lead to.
+    pub fn ends(&self) -> impl Iterator<Item = &N> {
+        self.ends.iter().map(|&index| &self.states[index])
+    }
+
+    /// Every state that's on at least one of the paths.
+    pub fn states(&self) -> impl Iterator<Item = &N> {
+        let mut on_path = vec![false; self.states.len()];
+        let mut stack = self.ends.clone();
+        while let Some(index) = stack.pop() {
+            if !on_path[index] {
+                on_path[index] = true;
+                stack.extend(self.parents_of(index));
+            }
+        }
+        self.states
+            .iter()
+            .zip(on_path)
+            .filter(|(_, on_path)| *on_path)
+            .map(|(state, _)| state)
+    }
+
+    /// Every path, from a start to a goal. There can be exponentially many
+    /// of them, prefer [`OptimalPaths::states`] when possible.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        fn extend<N: Clone>(
+            paths: &OptimalPaths<N>,
+            index: usize,
+            suffix: &mut Vec<usize>,
+            result: &mut Vec<Vec<N>>,
+        ) {
+            suffix.push(index);
+            if paths.parents[index].is_none() {
+                result.push(
+                    suffix
+                        .iter()
+                        .rev()
+                        .map(|&i| paths.states[i].clone())
+                        .collect(),
+                );
+            }
+            for parent in paths.parents_of(index) {
+                extend(paths, parent, suffix, result);
+            }
+            suffix.pop();
+        }
+
+        let mut result = Vec::new();
+        for &end in &self.ends {
+            extend(self, end, &mut Vec::new(), &mut result);
+        }
+        result
+    }
+}
+
+/// Distances of every position of a maze from its start and to its end, to
+/// find the shortcuts through its walls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMaps {
+    pub start: Pos,
+    pub end: Pos,
+    /// Number of steps from the start to every position, or `None` if it
+    /// can't be reached.
+    pub from_start: Grid<Option<usize>>,
+    /// Number of steps from every position to the end, or `None` if the end
+    /// can't be reached from it.
+    pub to_end: Grid<Option<usize>>,
+}
+
+/// Jump through the walls of a maze, see [`DistanceMaps::shortcuts`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub from: Pos,
+    pub to: Pos,
+    /// Number of steps taken by the jump.
+    pub length: usize,
+    /// Number of steps saved on the shortest path from the start to the end.
+    pub saving: usize,
+}
+
+impl DistanceMaps {
+    /// Distance maps of a maze where steps are orthogonal, and can only be
+    /// taken between tiles that are `passable`.
+    pub fn new<T>(grid: &Grid<T>, start: &Pos, end: &Pos, passable: impl Fn(&T) -> bool) -> Self {
+        let distances = |from: &Pos| {
+            let starts = grid.get(from).is_some_and(&passable).then(|| from.clone());
+            let distances = bfs_all(starts, |pos| {
+                grid.neighbours4(pos).filter(|next| passable(&grid[next]))
+            });
+            distance_grid(&distances, grid.size())
+        };
+        DistanceMaps {
+            start: start.clone(),
+            end: end.clone(),
+            from_start: distances(start),
+            to_end: distances(end),
+        }
+    }
+
+    /// Number of steps of the shortest path from the start to the end.
+    pub fn shortest(&self) -> Option<usize> {
+        self.from_start.get(&self.end).copied().flatten()
+    }
+
+    /// Every jump of at most `max_length` steps (in Manhattan distance)
+    /// between two positions of a path from the start to the end, that
+    /// saves at least `min_saving` steps (and at least one) on the shortest
+    /// path.
+    pub fn shortcuts(&self, max_length: usize, min_saving: usize) -> Vec<Shortcut> {
+        self.fold_shortcuts(
+            max_length,
+            min_saving,
+            Vec::new,
+            |mut shortcuts, shortcut| {
+                shortcuts.push(shortcut);
+                shortcuts
+            },
+        )
+        .concat()
+    }
+
+    /// Number of shortcuts, see [`DistanceMaps::shortcuts`].
+    pub fn count_shortcuts(&self, max_length: usize, min_saving: usize) -> usize {
+        self.fold_shortcuts(max_length, min_saving, || 0, |count, _| count + 1)
+            .into_iter()
+            .sum()
+    }
+
+    /// Fold the shortcuts starting in every band of rows on its own thread.
+    fn fold_shortcuts<A: Send>(
+        &self,
+        max_length: usize,
+        min_saving: usize,
+        init: impl Fn() -> A + Sync,
+        fold: impl Fn(A, Shortcut) -> A + Sync,
+    ) -> Vec<A> {
+        let Some(shortest) = self.shortest() else {
+            return Vec::new();
+        };
+        let size = self.from_start.size();
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let band = size.y.div_ceil(threads).max(1);
+
+        let fold_band = |rows: std::ops::Range<usize>| {
+            let mut acc = init();
+            for y in rows {
+                for x in 0..size.x {
+                    let from = Pos::new(x, y);
+                    let Some(to_from) = self.from_start[&from] else {
+                        continue;
+                    };
+                    let min_y = y.saturating_sub(max_length);
+                    let max_y = (y + max_length).min(size.y - 1);
+                    for to_y in min_y..=max_y {
+                        let reach = max_length - to_y.abs_diff(y);
+                        let min_x = x.saturating_sub(reach);
+                        let max_x = (x + reach).min(size.x - 1);
+                        for to_x in min_x..=max_x {
+                            let to = Pos::new(to_x, to_y);
+                            let Some(to_end) = self.to_end[&to] else {
+                                continue;
+                            };
+                            let length = to_y.abs_diff(y) + to_x.abs_diff(x);
+                            let saving = shortest.saturating_sub(to_from + length + to_end);
+                            if saving > 0 && saving >= min_saving {
+                                acc = fold(
+                                    acc,
+                                    Shortcut {
+                                        from: from.clone(),
+                                        to,
+                                        length,
+                                        saving,
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            acc
+        };
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..size.y)
+                .step_by(band)
+                .map(|start| {
+                    let fold_band = &fold_band;
+                    scope.spawn(move || fold_band(start..(start + band).min(size.y)))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("shortcut thread panicked"))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::Bounded;
+
+    fn maze() -> Grid<bool> {
+        // walls of a small maze with two shortest paths around a pillar
+        let rows = ["....", ".##.", "....", "#..."];
+        Grid::from_fn(Pos::new(4, 4), |pos| rows[pos.y].as_bytes()[pos.x] == b'#')
+    }
+
+    fn open_neighbours<'a>(grid: &'a Grid<bool>, pos: &Pos) -> impl Iterator<Item = Pos> + 'a {
+        grid.neighbours4(pos)
+            .filter(|pos| !grid[pos])
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn unweighted() {
+        let grid = maze();
+        let start = Pos::new(0, 0);
+        let end = Pos::new(3, 3);
+        let path = bfs(
+            [start.clone()],
+            |pos| open_neighbours(&grid, pos),
+            |pos| *pos == end,
+        );
+        assert_eq!(path.map(|path| path.len()), Some(7));
+
+        let distances = bfs_all([start.clone()], |pos| open_neighbours(&grid, pos));
+        assert_eq!(distances.len(), 13);
+        assert_eq!(distances[&end], 6);
+        let distance_grid = distance_grid(&distances, grid.size());
+        assert_eq!(distance_grid[&Pos::new(0, 2)], Some(2));
+        assert_eq!(distance_grid[&Pos::new(1, 1)], None);
+
+        let weighted = |pos: &Pos| {
+            open_neighbours(&grid, pos)
+                .map(|pos| (pos, 1))
+                .collect::<Vec<_>>()
+        };
+        let (path, cost) = dijkstra([start.clone()], weighted, |pos| *pos == end).unwrap();
+        assert_eq!((path.len(), cost), (7, 6));
+        assert_eq!(dijkstra_all([start.clone()], weighted), distances);
+        assert_eq!(
+            bfs([start], |pos| open_neighbours(&grid, pos), |_| false),
+            None
+        );
+    }
+
+    #[test]
+    fn walker() {
+        let grid = maze();
+        let costs = WalkCosts::new(10);
+        let end = Pos::new(3, 2);
+        let moves =
+            |walker: &Walker| costs.moves(walker, &grid.bounded(), |pos| (!grid[pos]).then_some(1));
+        let heuristic = |walker: &Walker| walker.pos.manhattan_distance(&end);
+        let start = Walker::new(Pos::new(0, 0), Direction::East);
+
+        // a single turn, along the top row then down
+        let (path, cost) = astar([start.clone()], moves, heuristic, |walker| {
+            walker.pos == end
+        })
+        .unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(path.len(), 7);
+
+        let (paths, cost) = astar_all([start.clone()], moves, heuristic, |walker| {
+            walker.pos == end
+        })
+        .unwrap();
+        assert_eq!(cost, 15);
+        assert_eq!(paths.paths().len(), 1);
+        assert_eq!(paths.states().count(), 7);
+
+        // turning along with the step for free, both ways around the pillar
+        // are the cheapest
+        let costs = WalkCosts {
+            turn_in_place: false,
+            ..WalkCosts::new(0)
+        };
+        let moves =
+            |walker: &Walker| costs.moves(walker, &grid.bounded(), |pos| (!grid[pos]).then_some(1));
+        let (paths, cost) =
+            astar_all([start], moves, heuristic, |walker| walker.pos == end).unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(paths.paths().len(), 2);
+        assert_eq!(paths.ends().count(), 2);
+        assert_eq!(paths.states().count(), 11);
+    }
+
+    #[test]
+    fn runs() {
+        // turning along with the step, going at least 2 and at most 3 steps in a row
+        let costs = WalkCosts {
+            turn: 0,
+            turn_in_place: false,
+            min_run: 2,
+            max_run: 3,
+        };
+        let topology = Bounded::new(Pos::new(5, 5));
+        let start = Walker::new(Pos::new(0, 0), Direction::East);
+        assert_eq!(costs.moves(&start, &topology, |_| Some(1)).count(), 1);
+
+        let end = Pos::new(4, 4);
+        let (path, cost) = dijkstra(
+            [start],
+            |walker| costs.moves(walker, &topology, |_| Some(1)),
+            |walker| walker.pos == end && walker.run >= costs.min_run,
+        )
+        .unwrap();
+        assert_eq!(cost, 8);
+        assert!(path.iter().all(|walker| walker.run <= 3));
+
+        // without limits, the run doesn't tell walkers apart
+        let open = Bounded::new(Pos::new(60, 60));
+        let states = dijkstra_all([Walker::new(Pos::new(0, 0), Direction::East)], |walker| {
+            WalkCosts::new(1000).moves(walker, &open, |_| Some(1))
+        });
+        assert_eq!(states.len(), 60 * 60 * 4);
+        assert!(states.keys().all(|walker| walker.run == 0));
+
+        // with only a minimum, runs are counted up to it
+        let costs = WalkCosts {
+            min_run: 2,
+            ..WalkCosts::new(1000)
+        };
+        let states = dijkstra_all([Walker::new(Pos::new(0, 0), Direction::East)], |walker| {
+            costs.moves(walker, &open, |_| Some(1))
+        });
+        assert_eq!(states.keys().map(|walker| walker.run).max(), Some(2));
+    }
+
+    #[test]
+    fn shortcuts() {
+        let rows = ["#####", "#S#E#", "#.#.#", "#...#", "#####"];
+        let grid = Grid::from_fn(Pos::new(5, 5), |pos| rows[pos.y].as_bytes()[pos.x]);
+        let maps = DistanceMaps::new(&grid, &Pos::new(1, 1), &Pos::new(3, 1), |tile| {
+            *tile != b'#'
+        });
+        assert_eq!(maps.shortest(), Some(6));
+        assert_eq!(maps.to_end[&Pos::new(1, 3)], Some(4));
+        assert_eq!(maps.from_start[&Pos::new(2, 1)], None);
+
+        assert_eq!(
+            maps.shortcuts(2, 3),
+            [Shortcut {
+                from: Pos::new(1, 1),
+                to: Pos::new(3, 1),
+                length: 2,
+                saving: 4,
+            }]
+        );
+        let mut savings: Vec<_> = maps.shortcuts(4, 0).into_iter().map(|s| s.saving).collect();
+        savings.sort();
+        assert_eq!(maps.count_shortcuts(4, 0), savings.len());
+        assert_eq!(savings, [2, 2, 2, 4]);
+
+        let outside = DistanceMaps::new(&grid, &Pos::new(1, 1), &Pos::new(9, 9), |tile| {
+            *tile != b'#'
+        });
+        assert_eq!(outside.shortest(), None);
+        assert_eq!(outside.count_shortcuts(4, 0), 0);
+    }
+}
@@ -0,0 +1,251 @@
+use ndarray::Array2;
+use rustc_hash::FxHashMap;
+
+use crate::navigation::{Direction, Vec2D};
+
+type Pos = Vec2D<isize>;
+
+/// Tiles on an unbounded plane, where only the positions that were set are
+/// stored. Positions have the same orientation as in [`Grid`](crate::grid::Grid),
+/// but can be negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<Pos, T>,
+    /// Smallest and largest coordinates of the tiles, both inclusive.
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: FxHashMap::default(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sparse grid with the tiles of `cells` that match the predicate, with
+    /// the top left tile at the origin.
+    pub fn from_array(cells: Array2<T>, mut keep: impl FnMut(&T) -> bool) -> Self {
+        let width = cells.ncols().max(1);
+        cells
+            .into_iter()
+            .enumerate()
+            .filter(|(_, tile)| keep(tile))
+            .map(|(i, tile)| (Pos::new((i % width) as isize, (i / width) as isize), tile))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.cells.contains_key(pos)
+    }
+
+    pub fn get(&self, pos: &Pos) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &Pos) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    /// Set a tile, growing the bounds if needed. Returns the previous tile.
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds.take() {
+            None => (pos.clone(), pos.clone()),
+            Some((min, max)) => (
+                Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    /// Unset a tile. The bounds aren't shrunk, see [`SparseGrid::shrink_bounds`].
+    pub fn remove(&mut self, pos: &Pos) -> Option<T> {
+        self.cells.remove(pos)
+    }
+
+    /// Unset every tile, keeping the memory to set new ones.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// Recompute the bounds so that they fit the remaining tiles.
+    pub fn shrink_bounds(&mut self) {
+        self.bounds = None;
+        for pos in self.cells.keys() {
+            let (min, max) = self.bounds.get_or_insert((pos.clone(), pos.clone()));
+            *min = Pos::new(min.x.min(pos.x), min.y.min(pos.y));
+            *max = Pos::new(max.x.max(pos.x), max.y.max(pos.y));
+        }
+    }
+
+    /// Smallest and largest coordinates of the tiles (both inclusive), or
+    /// `None` if no tile was ever set.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds.clone()
+    }
+
+    /// Number of columns and rows of the bounding box.
+    pub fn size(&self) -> Vec2D<usize> {
+        self.bounds
+            .as_ref()
+            .map(|(min, max)| Vec2D::new(max.x.abs_diff(min.x) + 1, max.y.abs_diff(min.y) + 1))
+            .unwrap_or_default()
+    }
+
+    /// Number of positions in the bounding box, set or not.
+    pub fn area(&self) -> usize {
+        let size = self.size();
+        size.x * size.y
+    }
+
+    /// Every tile along with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Pos, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Pos> {
+        self.cells.keys()
+    }
+
+    /// Tiles next to `pos` in the given directions that are set, along with
+    /// their positions.
+    pub fn neighbours_in<'a>(
+        &'a self,
+        pos: &Pos,
+        directions: impl IntoIterator<Item = Direction> + 'a,
+    ) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        let pos = pos.clone();
+        directions.into_iter().filter_map(move |direction| {
+            let neighbour = &pos + &Pos::from(direction);
+            self.cells.get(&neighbour).map(|tile| (neighbour, tile))
+        })
+    }
+
+    /// Orthogonal neighbours of `pos` that are set.
+    pub fn neighbours4(&self, pos: &Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_in(pos, Direction::get_all_orthogonal())
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` that are set.
+    pub fn neighbours8(&self, pos: &Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_in(pos, Direction::get_all())
+    }
+
+    /// Dense copy of the bounding box, where unset positions are `empty`.
+    /// The top left tile of the array is at the smallest coordinates of the
+    /// bounds.
+    pub fn to_array(&self, empty: T) -> Array2<T>
+    where
+        T: Clone,
+    {
+        let size = self.size();
+        let mut cells = Array2::from_elem((size.y, size.x), empty);
+        if let Some((min, _)) = &self.bounds {
+            for (pos, tile) in &self.cells {
+                cells[(pos.y.abs_diff(min.y), pos.x.abs_diff(min.x))] = tile.clone();
+            }
+        }
+        cells
+    }
+
+    /// Rows of the bounding box separated by newlines, where every position is
+    /// drawn by `draw`.
+    pub fn render(&self, mut draw: impl FnMut(Option<&T>) -> char) -> String {
+        let Some((min, max)) = &self.bounds else {
+            return String::new();
+        };
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| draw(self.cells.get(&Pos::new(x, y))))
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Pos, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Pos, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.area(), 0);
+        grid.insert(Pos::new(2, -1), 'a');
+        grid.insert(Pos::new(-1, 1), 'b');
+        assert_eq!(grid.bounds(), Some((Pos::new(-1, -1), Pos::new(2, 1))));
+        assert_eq!(grid.size(), Vec2D::new(4, 3));
+        assert_eq!(grid.area(), 12);
+
+        assert_eq!(grid.remove(&Pos::new(2, -1)), Some('a'));
+        assert_eq!(grid.area(), 12);
+        grid.shrink_bounds();
+        assert_eq!(grid.bounds(), Some((Pos::new(-1, 1), Pos::new(-1, 1))));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid: SparseGrid<()> = [Pos::new(0, 0), Pos::new(1, 0), Pos::new(-1, -1)]
+            .into_iter()
+            .map(|pos| (pos, ()))
+            .collect();
+        assert_eq!(
+            grid.neighbours4(&Pos::new(0, 0))
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>(),
+            [Pos::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours8(&Pos::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(&Pos::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn conversions() {
+        let cells = ndarray::arr2(&[['#', '.', '.'], ['.', '.', '#']]);
+        let mut grid = SparseGrid::from_array(cells, |tile| *tile == '#');
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(&Pos::new(2, 1)));
+
+        grid.insert(Pos::new(-1, 0), 'x');
+        assert_eq!(grid.render(|tile| *tile.unwrap_or(&'.')), "x#..\n...#");
+        assert_eq!(
+            grid.to_array(' '),
+            ndarray::arr2(&[['x', '#', ' ', ' '], [' ', ' ', ' ', '#']])
+        );
+    }
+}